@@ -0,0 +1,455 @@
+use crate::DATA_DIR_ARG;
+use crate::history::{HistoryEntry, Outcome};
+use crate::ordering::get_next_index;
+use crate::persistence::{
+    HistoryState, PersistenceError, WorkoutsState, read_history_state, read_workouts_state,
+    write_history_state, write_workouts_state,
+};
+use crate::workout::{
+    Program, Workout, get_index_after_move, get_index_after_removal, is_valid_workout_text,
+};
+use chrono::Local;
+use rand::RngCore;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+pub const USAGE: &str = "\
+Usage: workout-iterator [--data-dir <dir>] [<command>]
+
+Without a known command the window is opened. Positions are 1-based.
+
+Commands:
+  current              Print the current workout
+  next                 Log the current workout as completed and advance
+  list                 List the workouts, marking the current one
+  add <text>           Append a workout
+  remove <position>    Remove the workout at position
+  move <from> <to>     Move a workout to another position
+  set-index <position> Make the workout at position the current one
+  export               Print the persisted state as JSON
+  help                 Print this message";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Current,
+    Next,
+    List,
+    Add(String),
+    Remove(usize),
+    Move(usize, usize),
+    SetIndex(usize),
+    Export,
+    Help,
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    MissingArgument(&'static str),
+    InvalidPosition(String),
+    PositionOutOfRange { position: usize, count: usize },
+    InvalidWorkout(String),
+    EmptyRotation,
+    Persistence(PersistenceError),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingArgument(argument) => write!(f, "missing argument <{}>", argument),
+            CliError::InvalidPosition(value) => write!(f, "'{}' is not a valid position", value),
+            CliError::PositionOutOfRange { position, count } => {
+                write!(f, "position {} is out of range 1..={}", position, count)
+            }
+            CliError::InvalidWorkout(text) => {
+                write!(f, "'{}' is empty or already part of the rotation", text)
+            }
+            CliError::EmptyRotation => write!(f, "the rotation has no workouts"),
+            CliError::Persistence(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<PersistenceError> for CliError {
+    fn from(error: PersistenceError) -> Self {
+        CliError::Persistence(error)
+    }
+}
+
+/// What a command did, so the caller knows what has to be persisted.
+#[derive(Debug, Default)]
+pub struct CommandOutput {
+    pub text: String,
+    pub changed: bool,
+    pub completed: Option<Workout>,
+}
+
+impl CommandOutput {
+    fn unchanged(text: String) -> CommandOutput {
+        CommandOutput {
+            text,
+            ..CommandOutput::default()
+        }
+    }
+
+    fn changed(text: String) -> CommandOutput {
+        CommandOutput {
+            text,
+            changed: true,
+            ..CommandOutput::default()
+        }
+    }
+}
+
+/// Returns `None` when no known command is given and the window should be opened instead.
+pub fn parse_command(args: impl Iterator<Item = String>) -> Result<Option<Command>, CliError> {
+    let mut args = skip_data_dir_arg(args).into_iter();
+    let command = match args.next() {
+        None => return Ok(None),
+        Some(c) => c,
+    };
+
+    let command = match command.as_str() {
+        "current" => Command::Current,
+        "next" => Command::Next,
+        "list" => Command::List,
+        "add" => Command::Add(parse_text(args)?),
+        "remove" => Command::Remove(parse_position(args.next(), "position")?),
+        "move" => Command::Move(
+            parse_position(args.next(), "from")?,
+            parse_position(args.next(), "to")?,
+        ),
+        "set-index" => Command::SetIndex(parse_position(args.next(), "position")?),
+        "export" => Command::Export,
+        "help" | "--help" | "-h" => Command::Help,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(command))
+}
+
+/// Executes the command against the persisted state and returns the text to print.
+pub fn run_command(command: Command) -> Result<String, CliError> {
+    if command == Command::Help {
+        return Ok(USAGE.to_owned());
+    }
+
+    let mut workouts_state = read_workouts_state()?;
+    if command == Command::Export {
+        return Ok(serde_json::to_string_pretty(&workouts_state).map_err(PersistenceError::from)?);
+    }
+
+    let active_program = workouts_state.active_program;
+    let program = workouts_state
+        .programs
+        .iter_mut()
+        .find(|p| p.id == active_program)
+        .ok_or(PersistenceError::MalformedDocument(
+            "active program does not exist",
+        ))?;
+    let mut history_state = if command == Command::Next || program.schedule.is_enabled() {
+        read_history_state()?
+    } else {
+        HistoryState::default()
+    };
+    let mut rng = rand::rng();
+    let is_rescheduled =
+        program.apply_schedule(Local::now().date_naive(), &history_state.entries, &mut rng);
+    let output = execute_command(command, program, &history_state.entries, &mut rng)?;
+
+    if let Some(workout) = &output.completed {
+        history_state.entries.push(HistoryEntry::new(
+            workout,
+            Local::now(),
+            Outcome::Completed,
+            None,
+            None,
+        ));
+        write_history_state(&history_state)?;
+        program
+            .schedule
+            .set_due_from(Local::now().date_naive(), true);
+    }
+    if output.changed || is_rescheduled {
+        write_workouts_state(WorkoutsState::new(
+            workouts_state.active_program,
+            workouts_state.programs,
+        ))?;
+    }
+
+    Ok(output.text)
+}
+
+fn execute_command(
+    command: Command,
+    program: &mut Program,
+    history: &[HistoryEntry],
+    rng: &mut dyn RngCore,
+) -> Result<CommandOutput, CliError> {
+    let count = program.workouts.len();
+    let output = match command {
+        Command::Current => CommandOutput::unchanged(get_current_text(program)?),
+        Command::Next => {
+            let completed = get_current(program)?.clone();
+            program.index = get_next_index(
+                program.ordering,
+                &program.workouts,
+                program.index,
+                program.filter.as_ref(),
+                &mut program.cycle,
+                history,
+                rng,
+            );
+            CommandOutput {
+                completed: Some(completed),
+                ..CommandOutput::changed(get_current_text(program)?)
+            }
+        }
+        Command::List => CommandOutput::unchanged(format_list(program)),
+        Command::Add(text) => {
+            if !is_valid_workout_text(&text, &program.workouts, program.allow_duplicates) {
+                return Err(CliError::InvalidWorkout(text));
+            }
+            program.workouts.push(Workout::new(text));
+            CommandOutput::changed(format_list(program))
+        }
+        Command::Remove(position) => {
+            let position = to_index(position, count)?;
+            program.workouts.remove(position);
+            program.index = get_index_after_removal(program.index, position);
+            CommandOutput::changed(format_list(program))
+        }
+        Command::Move(from, to) => {
+            let from = to_index(from, count)?;
+            let to = to_index(to, count)?;
+            let workout = program.workouts.remove(from);
+            program.workouts.insert(to, workout);
+            program.index = get_index_after_move(program.index, from, to);
+            CommandOutput::changed(format_list(program))
+        }
+        Command::SetIndex(position) => {
+            program.index = to_index(position, count)?;
+            CommandOutput::changed(get_current_text(program)?)
+        }
+        Command::Export | Command::Help => CommandOutput::default(),
+    };
+
+    Ok(output)
+}
+
+fn skip_data_dir_arg(mut args: impl Iterator<Item = String>) -> Vec<String> {
+    let mut remaining = vec![];
+    while let Some(arg) = args.next() {
+        if arg == DATA_DIR_ARG {
+            args.next();
+        } else if !arg.starts_with(&format!("{}=", DATA_DIR_ARG)) {
+            remaining.push(arg);
+        }
+    }
+
+    remaining
+}
+
+fn parse_text(args: impl Iterator<Item = String>) -> Result<String, CliError> {
+    let text = args.collect::<Vec<_>>().join(" ");
+    if text.trim().is_empty() {
+        return Err(CliError::MissingArgument("text"));
+    }
+
+    Ok(text)
+}
+
+fn parse_position(value: Option<String>, name: &'static str) -> Result<usize, CliError> {
+    let value = value.ok_or(CliError::MissingArgument(name))?;
+
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|p| *p > 0)
+        .ok_or(CliError::InvalidPosition(value))
+}
+
+fn to_index(position: usize, count: usize) -> Result<usize, CliError> {
+    if position == 0 || position > count {
+        return Err(CliError::PositionOutOfRange { position, count });
+    }
+
+    Ok(position - 1)
+}
+
+fn get_current(program: &Program) -> Result<&Workout, CliError> {
+    program
+        .workouts
+        .get(program.index)
+        .ok_or(CliError::EmptyRotation)
+}
+
+fn get_current_text(program: &Program) -> Result<String, CliError> {
+    get_current(program).map(|w| w.text.clone())
+}
+
+fn format_list(program: &Program) -> String {
+    program
+        .workouts
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            let marker = if i == program.index { '*' } else { ' ' };
+            format!("{} {}. {}", marker, i + 1, w.text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{CliError, Command, CommandOutput, execute_command, parse_command};
+    use crate::workout::{LabelFilter, Program, Workout};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn create_program(index: usize, texts: &[&str]) -> Program {
+        Program {
+            index,
+            workouts: texts.iter().map(|t| Workout::new(t.to_string())).collect(),
+            ..Program::default()
+        }
+    }
+
+    fn execute(command: Command, program: &mut Program) -> Result<CommandOutput, CliError> {
+        execute_command(command, program, &[], &mut StdRng::seed_from_u64(1))
+    }
+
+    fn get_texts(program: &Program) -> Vec<&str> {
+        program.workouts.iter().map(|w| w.text.as_str()).collect()
+    }
+
+    fn to_args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_command_given_no_args_should_return_none() {
+        assert!(matches!(parse_command(to_args(&[])), Ok(None)))
+    }
+
+    #[test]
+    fn test_parse_command_given_data_dir_arg_should_skip_it() {
+        let command = parse_command(to_args(&["--data-dir", "/tmp/workouts", "move", "1", "3"]));
+
+        assert!(matches!(command, Ok(Some(Command::Move(1, 3)))))
+    }
+
+    #[test]
+    fn test_parse_command_given_zero_position_should_fail() {
+        let command = parse_command(to_args(&["remove", "0"]));
+
+        assert!(matches!(command, Err(CliError::InvalidPosition(_))))
+    }
+
+    #[test]
+    fn test_parse_command_given_add_without_text_should_fail() {
+        let command = parse_command(to_args(&["add", " "]));
+
+        assert!(matches!(command, Err(CliError::MissingArgument("text"))))
+    }
+
+    #[test]
+    fn test_parse_command_given_unknown_argument_should_return_none() {
+        let command = parse_command(to_args(&["jump"]));
+
+        assert!(matches!(command, Ok(None)))
+    }
+
+    #[test]
+    fn test_execute_command_given_next_on_last_workout_should_wrap() {
+        let mut program = create_program(1, &["workout1", "workout2"]);
+
+        let output = execute(Command::Next, &mut program).unwrap();
+
+        assert_eq!(0, program.index);
+        assert_eq!("workout1", output.text);
+        assert_eq!("workout2", output.completed.unwrap().text)
+    }
+
+    #[test]
+    fn test_execute_command_given_next_with_filter_should_skip_non_matching() {
+        let mut program = create_program(0, &["workout1", "workout2", "workout3"]);
+        program.workouts[2].tags = vec![String::from("cardio")];
+        program.filter = Some(LabelFilter::Tag(String::from("cardio")));
+
+        let output = execute(Command::Next, &mut program).unwrap();
+
+        assert_eq!(2, program.index);
+        assert_eq!("workout3", output.text)
+    }
+
+    #[test]
+    fn test_execute_command_given_next_on_empty_rotation_should_fail() {
+        let mut program = create_program(0, &[]);
+
+        let result = execute(Command::Next, &mut program);
+
+        assert!(matches!(result, Err(CliError::EmptyRotation)))
+    }
+
+    #[test]
+    fn test_execute_command_given_duplicate_text_should_fail() {
+        let mut program = create_program(0, &["workout1"]);
+
+        let result = execute(Command::Add(String::from("workout1")), &mut program);
+
+        assert!(matches!(result, Err(CliError::InvalidWorkout(_))));
+        assert_eq!(1, program.workouts.len())
+    }
+
+    #[test]
+    fn test_execute_command_given_remove_before_index_should_keep_current() {
+        let mut program = create_program(2, &["workout1", "workout2", "workout3"]);
+
+        execute(Command::Remove(1), &mut program).unwrap();
+
+        assert_eq!(vec!["workout2", "workout3"], get_texts(&program));
+        assert_eq!(1, program.index)
+    }
+
+    #[test]
+    fn test_execute_command_given_move_should_reorder() {
+        let mut program = create_program(0, &["workout1", "workout2", "workout3"]);
+
+        execute(Command::Move(1, 3), &mut program).unwrap();
+
+        assert_eq!(
+            vec!["workout2", "workout3", "workout1"],
+            get_texts(&program)
+        )
+    }
+
+    #[test]
+    fn test_execute_command_given_move_of_current_should_keep_current() {
+        let mut program = create_program(0, &["workout1", "workout2", "workout3"]);
+
+        execute(Command::Move(1, 3), &mut program).unwrap();
+
+        assert_eq!(2, program.index);
+        assert_eq!("workout1", program.workouts[program.index].text)
+    }
+
+    #[test]
+    fn test_execute_command_given_set_index_out_of_range_should_fail() {
+        let mut program = create_program(0, &["workout1"]);
+
+        let result = execute(Command::SetIndex(2), &mut program);
+
+        assert!(matches!(
+            result,
+            Err(CliError::PositionOutOfRange {
+                position: 2,
+                count: 1
+            })
+        ));
+        assert_eq!(0, program.index)
+    }
+}
@@ -0,0 +1,255 @@
+use crate::workout::{Load, Workout};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    #[default]
+    Completed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub workout_id: Uuid,
+    pub workout_text: String,
+    pub completed_at: DateTime<Local>,
+    #[serde(default)]
+    pub outcome: Outcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u32>,
+    /// Sets logged during a guided session, in the order they were done.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sets: Vec<SetLog>,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        workout: &Workout,
+        completed_at: DateTime<Local>,
+        outcome: Outcome,
+        note: Option<String>,
+        duration_secs: Option<u32>,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            workout_id: workout.id,
+            workout_text: workout.text.clone(),
+            completed_at,
+            outcome,
+            note,
+            duration_secs,
+            sets: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetLog {
+    pub exercise: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reps: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<Load>,
+}
+
+impl Display for SetLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.reps, &self.load) {
+            (Some(reps), Some(load)) => write!(f, "{} @ {} {}", reps, load.amount, load.unit),
+            (Some(reps), None) => write!(f, "{}", reps),
+            (None, Some(load)) => write!(f, "@ {} {}", load.amount, load.unit),
+            (None, None) => write!(f, "done"),
+        }
+    }
+}
+
+/// Describes logged sets grouped by consecutive exercise, e.g. `Squat 5 @ 100 kg, 5 @ 100 kg`.
+pub fn describe_sets(sets: &[SetLog]) -> String {
+    let mut groups: Vec<(&str, Vec<String>)> = vec![];
+    for set in sets {
+        match groups.last_mut() {
+            Some((exercise, logs)) if *exercise == set.exercise => logs.push(set.to_string()),
+            _ => groups.push((&set.exercise, vec![set.to_string()])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(exercise, logs)| format!("{} {}", exercise, logs.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub workout_id: Option<Uuid>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        let date = entry.completed_at.date_naive();
+
+        self.workout_id.is_none_or(|id| id == entry.workout_id)
+            && self.from.is_none_or(|from| date >= from)
+            && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/// Returns the entries matching the filter, most recent first.
+pub fn filter_entries<'a>(
+    entries: &'a [HistoryEntry],
+    filter: &HistoryFilter,
+) -> Vec<&'a HistoryEntry> {
+    let mut filtered: Vec<_> = entries.iter().filter(|e| filter.matches(e)).collect();
+    filtered.sort_by_key(|e| Reverse(e.completed_at));
+
+    filtered
+}
+
+pub fn count_skips(entries: &[HistoryEntry]) -> HashMap<Uuid, usize> {
+    entries
+        .iter()
+        .filter(|e| e.outcome == Outcome::Skipped)
+        .fold(HashMap::new(), |mut counts, e| {
+            *counts.entry(e.workout_id).or_default() += 1;
+            counts
+        })
+}
+
+pub fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::{
+        HistoryEntry, HistoryFilter, Outcome, SetLog, count_skips, describe_sets, filter_entries,
+        parse_date,
+    };
+    use crate::workout::{Load, LoadUnit, Workout};
+    use chrono::{Local, TimeZone};
+
+    fn create_entry(workout: &Workout, year: i32, month: u32, day: u32) -> HistoryEntry {
+        let completed_at = Local.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap();
+
+        HistoryEntry::new(workout, completed_at, Outcome::Completed, None, None)
+    }
+
+    #[test]
+    fn test_filter_entries_given_default_filter_should_return_all_newest_first() {
+        let workout = Workout::new(String::from("workout1"));
+        let entries = vec![
+            create_entry(&workout, 2026, 1, 1),
+            create_entry(&workout, 2026, 1, 3),
+            create_entry(&workout, 2026, 1, 2),
+        ];
+
+        let filtered = filter_entries(&entries, &HistoryFilter::default());
+
+        assert_eq!(
+            vec![
+                parse_date("2026-01-03"),
+                parse_date("2026-01-02"),
+                parse_date("2026-01-01"),
+            ],
+            filtered
+                .iter()
+                .map(|e| Some(e.completed_at.date_naive()))
+                .collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn test_filter_entries_given_workout_should_return_only_its_entries() {
+        let workout1 = Workout::new(String::from("workout1"));
+        let workout2 = Workout::new(String::from("workout2"));
+        let entries = vec![
+            create_entry(&workout1, 2026, 1, 1),
+            create_entry(&workout2, 2026, 1, 2),
+        ];
+        let filter = HistoryFilter {
+            workout_id: Some(workout2.id),
+            ..HistoryFilter::default()
+        };
+
+        let filtered = filter_entries(&entries, &filter);
+
+        assert_eq!(1, filtered.len());
+        assert_eq!(workout2.id, filtered[0].workout_id)
+    }
+
+    #[test]
+    fn test_filter_entries_given_date_range_should_include_bounds() {
+        let workout = Workout::new(String::from("workout1"));
+        let entries = vec![
+            create_entry(&workout, 2026, 1, 1),
+            create_entry(&workout, 2026, 1, 5),
+            create_entry(&workout, 2026, 1, 10),
+            create_entry(&workout, 2026, 1, 11),
+        ];
+        let filter = HistoryFilter {
+            from: parse_date("2026-01-05"),
+            to: parse_date("2026-01-10"),
+            ..HistoryFilter::default()
+        };
+
+        assert_eq!(2, filter_entries(&entries, &filter).len())
+    }
+
+    #[test]
+    fn test_count_skips_should_count_only_skipped_entries_per_workout() {
+        let workout1 = Workout::new(String::from("workout1"));
+        let workout2 = Workout::new(String::from("workout2"));
+        let skip = |workout: &Workout| HistoryEntry {
+            outcome: Outcome::Skipped,
+            ..create_entry(workout, 2026, 1, 1)
+        };
+        let entries = vec![
+            skip(&workout1),
+            skip(&workout1),
+            create_entry(&workout1, 2026, 1, 2),
+            create_entry(&workout2, 2026, 1, 3),
+        ];
+
+        let counts = count_skips(&entries);
+
+        assert_eq!(Some(&2), counts.get(&workout1.id));
+        assert_eq!(None, counts.get(&workout2.id))
+    }
+
+    #[test]
+    fn test_parse_date_given_invalid_input_should_return_none() {
+        assert_eq!(None, parse_date("18.10.2026"))
+    }
+
+    #[test]
+    fn test_describe_sets_should_group_consecutive_sets_by_exercise() {
+        let squat = |reps| SetLog {
+            exercise: String::from("Squat"),
+            reps: Some(reps),
+            load: Some(Load {
+                amount: 100.0,
+                unit: LoadUnit::Kg,
+            }),
+        };
+        let plank = SetLog {
+            exercise: String::from("Plank"),
+            reps: None,
+            load: None,
+        };
+
+        let description = describe_sets(&[squat(5), squat(4), plank]);
+
+        assert_eq!("Squat 5 @ 100 kg, 4 @ 100 kg; Plank done", description)
+    }
+}
@@ -1,46 +1,142 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod cli;
 mod helper;
+mod history;
+mod ordering;
 mod persistence;
+mod progression;
+mod schedule;
+mod search;
+mod session;
+mod shortcut;
+mod stats;
+mod timer;
+mod transfer;
 mod ui;
+mod undo;
+mod workout;
 
+use crate::cli::{USAGE, parse_command, run_command};
 use crate::helper::modal;
+use crate::history::{
+    HistoryEntry, HistoryFilter, Outcome, count_skips, describe_sets, filter_entries, parse_date,
+};
+use crate::ordering::{OrderingMode, get_next_index};
 use crate::persistence::{
-    Position, WorkoutsState, log_error, read_window_state, read_workouts_state, write_window_state,
+    HistoryState, PersistenceError, Position, WorkoutsState, get_workouts_backups, init_data_paths,
+    log_error, read_history_state, read_window_state, read_workouts_state, reset_workouts_index,
+    reset_workouts_state, restore_workouts_backup, write_history_state, write_window_state,
     write_workouts_state,
 };
+use crate::progression::suggest;
+use crate::schedule::{CatchUpPolicy, Day, ScheduleSettings, format_day};
+use crate::search::search_workout;
+use crate::session::Session;
+use crate::shortcut::{Shortcut, get_shortcut};
+use crate::stats::{count_sessions_per_week, get_streaks, get_volume_trend, get_workout_rates};
+use crate::timer::{Timer, TimerMode};
+use crate::transfer::{MergeMode, TransferFormat, apply_import, export_workouts, import_workouts};
+use crate::ui::chart::ChartPoint;
+use crate::ui::completion_dialog::{CompletionInput, create_completion_dialog};
 use crate::ui::confirmation_dialog::{
     ConfirmationPayload, ConfirmationTopic, create_confirmation_dialog,
 };
-use crate::ui::settings_page::{SettingsViewModel, create_settings_page};
-use crate::ui::{MainViewModel, Page, WINDOW_HEIGHT, WINDOW_WIDTH, create_main_page};
+use crate::ui::exercise_editor_page::{ExerciseEditorViewModel, create_exercise_editor_page};
+use crate::ui::help_overlay::create_help_overlay;
+use crate::ui::history_page::{HistoryViewModel, WorkoutFilterOption, create_history_page};
+use crate::ui::import_dialog::{ImportInput, create_import_dialog};
+use crate::ui::recovery_page::{RecoveryViewModel, create_recovery_page};
+use crate::ui::schedule_page::{
+    ScheduleInput, ScheduleMode, ScheduleViewModel, create_schedule_page,
+};
+use crate::ui::session_page::{SessionInput, SessionViewModel, create_session_page};
+use crate::ui::settings_page::{ListItem, SettingsViewModel, create_settings_page};
+use crate::ui::stats_page::{StatsChart, StatsViewModel, create_stats_page};
+use crate::ui::timer_panel::TimerViewModel;
+use crate::ui::transfer_page::{TransferInput, TransferViewModel, create_transfer_page};
+use crate::ui::{
+    ExerciseLine, LabelFilterOption, MainViewModel, Page, ProgramOption, WINDOW_HEIGHT,
+    WINDOW_WIDTH, WorkoutOption, create_main_page,
+};
+use crate::undo::{EditCommand, RotationSettings, UndoHistory};
+use crate::workout::{
+    LabelFilter, Program, Workout, format_exercises, get_label_filters, get_occurrences,
+    is_valid_workout_text, link_workout, move_workouts, parse_exercises, parse_tags,
+    unlink_workout, update_linked_workouts,
+};
 use bitflags::bitflags;
-use iced::Event::Window;
+use chrono::{Local, NaiveDate, Weekday};
+use iced::Event::{Mouse, Window};
+use iced::widget::text_editor;
 use iced::window::{Id, Settings};
-use iced::{Element, Point, Size, Subscription, Task, event, window};
+use iced::{Element, Point, Size, Subscription, Task, event, keyboard, mouse, time, window};
 use image::ImageFormat;
-use std::cmp::max;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 const ICON_BYTES: &[u8] = include_bytes!("../resources/icon.ico");
+const DATA_DIR_ARG: &str = "--data-dir";
+const STATS_WEEKS: u32 = 12;
+/// Trends show the most recent days with logged sets.
+const STATS_DAYS: usize = 30;
+const STATS_LABEL_CHARS: usize = 10;
+const DATE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// The timers read the clock on each tick, so this only bounds how often the display updates.
+const TIMER_TICK_INTERVAL: Duration = Duration::from_millis(100);
 
 fn main() -> iced::Result {
-    let workouts_state = read_workouts_state();
-    let workouts: Vec<_> = workouts_state
-        .workouts
-        .into_iter()
-        .map(Workout::new)
-        .collect();
+    if let Err(error) = init_data_paths(get_data_dir_arg(std::env::args().skip(1))) {
+        log_error(error.to_string()).ok();
+    }
+
+    let command = parse_command(std::env::args().skip(1));
+    if !matches!(command, Ok(None)) {
+        attach_console();
+    }
+    match command {
+        Ok(None) => {}
+        Ok(Some(command)) => match run_command(command) {
+            Ok(output) => {
+                println!("{}", output);
+                std::process::exit(0);
+            }
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        },
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    }
+
     let window_position = read_window_state();
     let mut app_state = AppState {
-        workout_index: workouts_state.index,
-        workouts: workouts.clone(),
         window_position: window_position.clone(),
         ..AppState::default()
     };
-    app_state
-        .operation_flags
-        .set(OperationFlags::CanClear, !workouts.is_empty());
+    let is_loaded = match read_workouts_state() {
+        Ok(workouts_state) => {
+            app_state.load_workouts_state(workouts_state);
+            true
+        }
+        Err(error) => {
+            app_state.start_recovery(error);
+            false
+        }
+    };
+    match read_history_state() {
+        Ok(history_state) => app_state.history = history_state,
+        Err(error) => {
+            log_error(error.to_string()).ok();
+        }
+    }
+    if is_loaded {
+        app_state.apply_schedule(get_today());
+    }
 
     iced::application("Workout Iterator", AppState::update, AppState::view)
         .window(Settings {
@@ -52,46 +148,179 @@ fn main() -> iced::Result {
             exit_on_close_request: false,
             ..Settings::default()
         })
-        .subscription(AppState::window_subscription)
+        .subscription(AppState::subscription)
         .resizable(false)
         .run_with(|| (app_state, window::get_latest().map(Message::WindowId)))
 }
 
+/// Release builds on Windows have no console of their own, so the output of a command goes to
+/// the console it was started from.
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // SAFETY: AttachConsole takes no pointers, failing only leaves the process without a console
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+fn get_today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Offers all workouts followed by the categories and tags in use.
+fn get_label_filter_options(workouts: &[Workout]) -> Vec<LabelFilterOption> {
+    std::iter::once(None)
+        .chain(get_label_filters(workouts).into_iter().map(Some))
+        .map(|filter| LabelFilterOption { filter })
+        .collect()
+}
+
+fn get_data_dir_arg(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    while let Some(arg) = args.next() {
+        if arg == DATA_DIR_ARG {
+            return args.next().map(PathBuf::from);
+        }
+
+        if let Some(value) = arg
+            .strip_prefix(DATA_DIR_ARG)
+            .and_then(|s| s.strip_prefix('='))
+        {
+            return Some(PathBuf::from(value));
+        }
+    }
+
+    None
+}
+
 bitflags! {
-    struct OperationFlags: u8 {
+    struct OperationFlags: u16 {
         const CanAdd = 1;
         const CanUpdate = 1 << 1;
         const CanDelete = 1 << 2;
         const CanClear = 1 << 3;
         const CanMoveUp = 1 << 4;
         const CanMoveDown = 1 << 5;
+        const CanEditExercises = 1 << 6;
+        const CanNameProgram = 1 << 7;
+        const CanDeleteProgram = 1 << 8;
+        const CanUndo = 1 << 9;
+        const CanRedo = 1 << 10;
+        const CanSetCurrent = 1 << 11;
+        const CanLink = 1 << 12;
+        const CanMoveToTop = 1 << 13;
+        const CanMoveToBottom = 1 << 14;
     }
 }
 
 struct AppState {
     window_id: Option<Id>,
-    workout_index: i8,
+    workout_index: usize,
     workouts: Vec<Workout>,
+    settings: RotationSettings,
+    cycle: Vec<Uuid>,
+    schedule: ScheduleSettings,
+    /// Date the schedule was last applied on.
+    today: NaiveDate,
+    rotation_filter: Option<LabelFilter>,
+    settings_filter: Option<LabelFilter>,
+    search_input: String,
+    /// Workouts checked in the list, which move together.
+    marked: Vec<Uuid>,
+    dragged: Option<Uuid>,
+    drag_target: Option<Uuid>,
+    schedule_input: Option<ScheduleInput>,
+    program_id: Uuid,
+    programs: Vec<Program>,
+    program_input: Option<String>,
     current_page: Page,
     show_confirmation: Option<ConfirmationTopic>,
     workout_selection: Option<Workout>,
     workout_input: Option<String>,
     operation_flags: OperationFlags,
     window_position: Option<Position>,
+    recovery: Option<Recovery>,
+    exercise_editor: text_editor::Content,
+    pick_weight_input: String,
+    notes_editor: text_editor::Content,
+    category_input: String,
+    tags_input: String,
+    history: HistoryState,
+    history_filter: HistoryFilterInput,
+    completion: Option<CompletionInput>,
+    transfer: TransferInput,
+    import: Option<ImportInput>,
+    undo_history: UndoHistory,
+    show_help: bool,
+    show_timer: bool,
+    timer: Timer,
+    timer_mode: TimerMode,
+    timer_amount: String,
+    /// Countdown between session sets, kept apart from the panel timer.
+    rest: Option<Timer>,
+    session: Option<Session>,
+    session_input: SessionInput,
+    stats_chart: StatsChart,
 }
 
 impl Default for AppState {
     fn default() -> AppState {
+        let program = Program::default();
+
         AppState {
             window_id: None,
             workout_index: 0,
             workouts: vec![],
+            settings: RotationSettings::default(),
+            cycle: vec![],
+            schedule: ScheduleSettings::default(),
+            today: get_today(),
+            rotation_filter: None,
+            settings_filter: None,
+            search_input: String::new(),
+            marked: vec![],
+            dragged: None,
+            drag_target: None,
+            schedule_input: None,
+            program_id: program.id,
+            programs: vec![program],
+            program_input: None,
             current_page: Page::Main,
             show_confirmation: None,
             workout_selection: None,
             workout_input: None,
             operation_flags: OperationFlags::empty(),
             window_position: None,
+            recovery: None,
+            exercise_editor: text_editor::Content::new(),
+            pick_weight_input: String::new(),
+            notes_editor: text_editor::Content::new(),
+            category_input: String::new(),
+            tags_input: String::new(),
+            history: HistoryState::default(),
+            history_filter: HistoryFilterInput::default(),
+            completion: None,
+            transfer: TransferInput::default(),
+            import: None,
+            undo_history: UndoHistory::default(),
+            show_help: false,
+            show_timer: false,
+            timer: Timer::default(),
+            timer_mode: TimerMode::default(),
+            timer_amount: String::new(),
+            rest: None,
+            session: None,
+            session_input: SessionInput::default(),
+            stats_chart: StatsChart::default(),
         }
     }
 }
@@ -100,7 +329,6 @@ impl AppState {
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::WindowId(id_option) => self.on_window_id(id_option),
-            Message::NextWorkout => self.on_next_workout(),
             Message::OpenSettings => self.on_open_settings(),
             Message::CloseSettings => self.on_close_settings(),
             Message::CloseConfirmationDialog(payload) => self.on_close_confirmation_dialog(payload),
@@ -108,12 +336,100 @@ impl AppState {
             Message::WorkoutInput(input_option) => self.on_workout_input(input_option),
             Message::AddWorkout => self.on_add_workout(),
             Message::UpdateWorkout => self.on_update_workout(),
-            Message::InitiateWorkoutDeletion => self.on_initiate_workout_deletion(),
-            Message::InitiateClearance => self.on_initiate_clearance(),
+            Message::DeleteWorkout => self.on_delete_workout(),
+            Message::ClearWorkouts => self.on_clear_workouts(),
             Message::MoveWorkoutUp => self.on_move_workout_up(),
             Message::MoveWorkoutDown => self.on_move_workout_down(),
+            Message::MoveWorkoutsToTop => self.on_move_workouts_to_top(),
+            Message::MoveWorkoutsToBottom => self.on_move_workouts_to_bottom(),
+            Message::MarkWorkout(id, is_marked) => self.on_mark_workout(id, is_marked),
+            Message::DragWorkout(id) => self.on_drag_workout(id),
+            Message::DragOverWorkout(id) => self.on_drag_over_workout(id),
+            Message::DropWorkout => self.on_drop_workout(),
+            Message::DragOutWorkout(id) => self.on_drag_out_workout(id),
             Message::WindowMoved(x, y) => self.on_window_moved(x, y),
             Message::WindowCloseRequest => self.on_window_close_request(),
+            Message::RestoreBackup(backup) => self.on_restore_backup(backup),
+            Message::ResetIndex => self.on_reset_index(),
+            Message::StartFresh => self.on_start_fresh(),
+            Message::OpenExerciseEditor => self.on_open_exercise_editor(),
+            Message::ExerciseEditorAction(action) => self.on_exercise_editor_action(action),
+            Message::SaveExercises => self.on_save_exercises(),
+            Message::CloseExerciseEditor => self.on_close_exercise_editor(),
+            Message::InitiateCompletion => self.on_initiate_logging(Outcome::Completed),
+            Message::InitiateSkip => self.on_initiate_logging(Outcome::Skipped),
+            Message::CompletionNoteInput(note) => self.on_completion_note_input(note),
+            Message::CompletionDurationInput(duration) => {
+                self.on_completion_duration_input(duration)
+            }
+            Message::CloseCompletionDialog(confirmed) => self.on_close_completion_dialog(confirmed),
+            Message::OpenHistory => self.on_open_history(),
+            Message::CloseHistory => self.on_close_history(),
+            Message::OpenStats => self.on_open_stats(),
+            Message::CloseStats => self.on_close_stats(),
+            Message::StatsChartSelection(chart) => self.on_stats_chart_selection(chart),
+            Message::HistoryWorkoutFilter(option) => self.on_history_workout_filter(option),
+            Message::HistoryFromInput(input) => self.on_history_from_input(input),
+            Message::HistoryToInput(input) => self.on_history_to_input(input),
+            Message::SwitchProgram(option) => self.on_switch_program(option),
+            Message::ProgramInput(input) => self.on_program_input(input),
+            Message::CreateProgram => self.on_create_program(),
+            Message::DuplicateProgram => self.on_duplicate_program(),
+            Message::RenameProgram => self.on_rename_program(),
+            Message::InitiateProgramDeletion => self.on_initiate_program_deletion(),
+            Message::OpenTransfer => self.on_open_transfer(),
+            Message::CloseTransfer => self.on_close_transfer(),
+            Message::TransferPathInput(path) => self.on_transfer_path_input(path),
+            Message::TransferFormatSelection(format) => self.on_transfer_format_selection(format),
+            Message::ExportWorkouts => self.on_export_workouts(),
+            Message::PreviewImport => self.on_preview_import(),
+            Message::MergeModeSelection(mode) => self.on_merge_mode_selection(mode),
+            Message::CloseImportDialog(confirmed) => self.on_close_import_dialog(confirmed),
+            Message::Undo => self.on_undo(),
+            Message::Redo => self.on_redo(),
+            Message::Shortcut(shortcut) => self.on_shortcut(shortcut),
+            Message::PreviousWorkout => self.on_previous_workout(),
+            Message::JumpToWorkout(option) => self.on_jump_to_workout(option),
+            Message::SetCurrentWorkout => self.on_set_current_workout(),
+            Message::OrderingSelection(mode) => self.on_ordering_selection(mode),
+            Message::AllowDuplicatesToggle(allow) => self.on_allow_duplicates_toggle(allow),
+            Message::ToggleLink => self.on_toggle_link(),
+            Message::StartSession => self.on_start_session(),
+            Message::SessionRepsInput(reps) => self.on_session_reps_input(reps),
+            Message::SessionLoadInput(load) => self.on_session_load_input(load),
+            Message::LogSet => self.on_log_set(),
+            Message::SkipSet => self.on_skip_set(),
+            Message::FinishSession => self.on_finish_session(),
+            Message::CancelSession => self.on_cancel_session(),
+            Message::ToggleTimerPanel => self.on_toggle_timer_panel(),
+            Message::ToggleTimer => self.on_toggle_timer(),
+            Message::ResetTimer => self.on_reset_timer(),
+            Message::TimerModeSelection(mode) => self.on_timer_mode_selection(mode),
+            Message::TimerAmountInput(amount) => self.on_timer_amount_input(amount),
+            Message::TimerTick(now) => self.on_timer_tick(now),
+            Message::DateCheck(today) => self.on_date_check(today),
+            Message::OpenSchedule => self.on_open_schedule(),
+            Message::CloseSchedule => self.on_close_schedule(),
+            Message::SaveSchedule => self.on_save_schedule(),
+            Message::ScheduleModeSelection(mode) => self.on_schedule_input(|i| i.mode = mode),
+            Message::ScheduleDayToggle(day, is_checked) => {
+                self.on_schedule_input(|i| i.toggle_day(day, is_checked))
+            }
+            Message::ScheduleOnInput(on) => self.on_schedule_input(|i| i.on = on),
+            Message::ScheduleOffInput(off) => self.on_schedule_input(|i| i.off = off),
+            Message::ScheduleStartInput(start) => self.on_schedule_input(|i| i.start = start),
+            Message::CatchUpPolicySelection(policy) => {
+                self.on_schedule_input(|i| i.policy = policy)
+            }
+            Message::PickWeightInput(pick_weight) => self.on_pick_weight_input(pick_weight),
+            Message::NotesEditorAction(action) => self.on_notes_editor_action(action),
+            Message::CategoryInput(category) => self.on_category_input(category),
+            Message::TagsInput(tags) => self.on_tags_input(tags),
+            Message::RotationFilterSelection(option) => self.on_rotation_filter_selection(option),
+            Message::SettingsFilterSelection(option) => self.on_settings_filter_selection(option),
+            Message::SearchInput(query) => self.on_search_input(query),
+            Message::SearchSubmit => self.on_search_submit(),
+            Message::ToggleHelp => self.on_toggle_help(),
         }
     }
 
@@ -123,16 +439,6 @@ impl AppState {
         Task::none()
     }
 
-    fn on_next_workout(&mut self) -> Task<Message> {
-        let count = self.workouts.len() as i8;
-        if count > 0 {
-            self.workout_index = (self.workout_index + 1) % count;
-            self.write_workouts_state();
-        }
-
-        Task::none()
-    }
-
     fn on_open_settings(&mut self) -> Task<Message> {
         self.current_page = Page::Settings;
 
@@ -141,6 +447,9 @@ impl AppState {
 
     fn on_close_settings(&mut self) -> Task<Message> {
         self.current_page = Page::Main;
+        self.program_input = None;
+        self.search_input = String::new();
+        self.reset_marks();
         self.reset_input();
         self.update_operation_flags();
 
@@ -151,8 +460,8 @@ impl AppState {
         self.show_confirmation = None;
         if payload.confirmed {
             match payload.topic {
-                ConfirmationTopic::WorkoutDeletion => self.delete_workout(),
-                ConfirmationTopic::Clearance => self.clear_workouts(),
+                ConfirmationTopic::ProgramDeletion => self.delete_program(),
+                ConfirmationTopic::ExportOverwrite => self.write_export(),
             };
         }
 
@@ -194,10 +503,11 @@ impl AppState {
             Some(s) => s,
         };
 
-        self.workouts.push(Workout::new(input));
         self.workout_input = None;
-        self.update_operation_flags();
-        self.write_workouts_state();
+        self.execute(EditCommand::Insert {
+            position: self.workouts.len(),
+            workout: Workout::new(input),
+        });
 
         Task::none()
     }
@@ -214,22 +524,43 @@ impl AppState {
         };
 
         if let Some(position) = self.get_position(workout) {
-            self.workouts[position].text = input;
-            self.update_operation_flags();
-            self.write_workouts_state();
+            let after = Workout {
+                text: input,
+                ..self.workouts[position].clone()
+            };
+            self.execute_update(position, after);
         }
 
         Task::none()
     }
 
-    fn on_initiate_workout_deletion(&mut self) -> Task<Message> {
-        self.show_confirmation = Some(ConfirmationTopic::WorkoutDeletion);
+    /// Removes the selection right away, since the removal can be undone.
+    fn on_delete_workout(&mut self) -> Task<Message> {
+        let position = match self
+            .workout_selection
+            .clone()
+            .and_then(|w| self.get_position(w))
+        {
+            None => return Task::none(),
+            Some(p) => p,
+        };
+
+        self.reset_input();
+        self.execute(EditCommand::Remove {
+            position,
+            workout: self.workouts[position].clone(),
+            index: self.workout_index,
+        });
 
         Task::none()
     }
 
-    fn on_initiate_clearance(&mut self) -> Task<Message> {
-        self.show_confirmation = Some(ConfirmationTopic::Clearance);
+    fn on_clear_workouts(&mut self) -> Task<Message> {
+        self.reset_input();
+        self.execute(EditCommand::ReplaceAll {
+            before: (self.workouts.clone(), self.workout_index),
+            after: (vec![], 0),
+        });
 
         Task::none()
     }
@@ -240,16 +571,15 @@ impl AppState {
             Some(w) => w,
         };
 
-        let position = match self.get_position(workout.clone()) {
+        let (from, to) = match self.get_position(workout.clone()).and_then(|p| {
+            self.get_adjacent_visible_position(p, true)
+                .map(|to| (p, to))
+        }) {
             None => return Task::none(),
-            Some(0) => return Task::none(),
-            Some(p) => p,
+            Some(m) => m,
         };
 
-        let removed = self.workouts.remove(position);
-        self.workouts.insert(position - 1, removed);
-        self.update_operation_flags();
-        self.write_workouts_state();
+        self.execute(EditCommand::Move { from, to });
 
         Task::none()
     }
@@ -260,16 +590,98 @@ impl AppState {
             Some(w) => w,
         };
 
-        let position = match self.get_position(workout.clone()) {
+        let (from, to) = match self.get_position(workout.clone()).and_then(|p| {
+            self.get_adjacent_visible_position(p, false)
+                .map(|to| (p, to))
+        }) {
             None => return Task::none(),
-            Some(p) if p >= self.workouts.len() - 1 => return Task::none(),
-            Some(p) => p,
+            Some(m) => m,
         };
 
-        let removed = self.workouts.remove(position);
-        self.workouts.insert(position + 1, removed);
+        self.execute(EditCommand::Move { from, to });
+
+        Task::none()
+    }
+
+    fn on_move_workouts_to_top(&mut self) -> Task<Message> {
+        let positions = self.get_moving_positions();
+        if !positions.is_empty() {
+            self.move_block(&positions, 0);
+        }
+
+        Task::none()
+    }
+
+    fn on_move_workouts_to_bottom(&mut self) -> Task<Message> {
+        let positions = self.get_moving_positions();
+        if !positions.is_empty() {
+            self.move_block(&positions, self.workouts.len());
+        }
+
+        Task::none()
+    }
+
+    fn on_mark_workout(&mut self, id: Uuid, is_marked: bool) -> Task<Message> {
+        self.marked.retain(|m| *m != id);
+        if is_marked {
+            self.marked.push(id);
+        }
         self.update_operation_flags();
-        self.write_workouts_state();
+
+        Task::none()
+    }
+
+    fn on_drag_workout(&mut self, id: Uuid) -> Task<Message> {
+        self.dragged = Some(id);
+        self.drag_target = None;
+
+        Task::none()
+    }
+
+    fn on_drag_over_workout(&mut self, id: Uuid) -> Task<Message> {
+        if self.dragged.is_some() {
+            self.drag_target = Some(id);
+        }
+
+        Task::none()
+    }
+
+    /// Ends a drag wherever the mouse is released. The dragged workout, or all marked ones if it
+    /// is marked, land before the target when dragged upwards and after it when dragged downwards.
+    /// Without a target the drag is cancelled.
+    fn on_drop_workout(&mut self) -> Task<Message> {
+        let (dragged, target) = match (self.dragged.take(), self.drag_target.take()) {
+            (Some(d), Some(t)) if d != t => (d, t),
+            _ => return Task::none(),
+        };
+        let (from, target) = match (self.get_id_position(dragged), self.get_id_position(target)) {
+            (Some(f), Some(t)) => (f, t),
+            _ => return Task::none(),
+        };
+
+        let positions = if self.marked.contains(&dragged) {
+            self.get_marked_positions()
+        } else {
+            vec![from]
+        };
+        if positions.contains(&target) {
+            return Task::none();
+        }
+        let before_target = positions.iter().filter(|p| **p < target).count();
+        let to = if from < target {
+            target - before_target + 1
+        } else {
+            target - before_target
+        };
+        self.move_block(&positions, to);
+
+        Task::none()
+    }
+
+    fn on_drag_out_workout(&mut self, id: Uuid) -> Task<Message> {
+        if self.drag_target == Some(id) {
+            self.drag_target = None;
+        }
 
         Task::none()
     }
@@ -294,260 +706,2843 @@ impl AppState {
         }
     }
 
-    fn delete_workout(&mut self) {
-        let workout = match self.workout_selection.clone() {
-            None => return,
-            Some(w) => w,
-        };
-
-        if let Some(position) = self.get_position(workout) {
-            self.workouts.remove(position);
-            if position <= self.workout_index as usize {
-                self.workout_index = max(self.workout_index - 1, 0);
-            }
+    fn on_restore_backup(&mut self, backup: PathBuf) -> Task<Message> {
+        self.finish_recovery(restore_workouts_backup(&backup));
 
-            self.reset_input();
-            self.update_operation_flags();
-            self.write_workouts_state();
-        }
+        Task::none()
     }
 
-    fn clear_workouts(&mut self) {
-        self.workouts.clear();
-        self.workout_index = 0;
-        self.reset_input();
-        self.update_operation_flags();
-        self.write_workouts_state();
-    }
+    fn on_reset_index(&mut self) -> Task<Message> {
+        self.finish_recovery(reset_workouts_index());
 
-    fn has_unique_input(&self) -> bool {
-        matches!(self.workout_input.clone(), Some(input) if self.workouts.iter().all(|s| !input.eq(&s.text)))
+        Task::none()
     }
 
-    fn get_valid_input(&mut self) -> Option<String> {
-        self.workout_input
-            .clone()
-            .filter(|s| !s.is_empty() && !self.workouts.iter().any(|w| w.text.eq(s)))
-    }
+    fn on_start_fresh(&mut self) -> Task<Message> {
+        self.finish_recovery(reset_workouts_state());
 
-    fn reset_input(&mut self) {
-        self.workout_selection = None;
-        self.workout_input = None;
+        Task::none()
     }
 
-    fn update_operation_flags(&mut self) {
-        self.operation_flags
-            .set(OperationFlags::CanAdd, self.has_unique_input());
-        self.operation_flags.set(
-            OperationFlags::CanUpdate,
-            self.workout_selection.is_some() && self.has_unique_input(),
-        );
-        self.operation_flags
-            .set(OperationFlags::CanDelete, self.workout_selection.is_some());
-        self.operation_flags
-            .set(OperationFlags::CanClear, !self.workouts.is_empty());
-        self.operation_flags.set(
-            OperationFlags::CanMoveUp,
-            self.workout_selection
-                .clone()
-                .and_then(|w| self.get_position(w))
-                .is_some_and(|p| p > 0),
-        );
-        self.operation_flags.set(
-            OperationFlags::CanMoveDown,
-            self.workout_selection
-                .clone()
-                .and_then(|w| self.get_position(w))
-                .is_some_and(|p| p < self.workouts.len() - 1),
-        );
+    fn on_open_exercise_editor(&mut self) -> Task<Message> {
+        let workout = match self.workout_selection.clone() {
+            None => return Task::none(),
+            Some(w) => w,
+        };
+
+        self.exercise_editor =
+            text_editor::Content::with_text(&format_exercises(&workout.exercises));
+        self.pick_weight_input = workout.pick_weight.to_string();
+        self.notes_editor = text_editor::Content::with_text(&workout.notes);
+        self.category_input = workout.category.unwrap_or_default();
+        self.tags_input = workout.tags.join(", ");
+        self.current_page = Page::ExerciseEditor;
+
+        Task::none()
     }
 
-    fn get_position(&self, workout: Workout) -> Option<usize> {
-        self.workouts.iter().position(|w| w.id == workout.id)
+    fn on_exercise_editor_action(&mut self, action: text_editor::Action) -> Task<Message> {
+        self.exercise_editor.perform(action);
+
+        Task::none()
     }
 
-    fn view(&self) -> Element<Message> {
-        let page = match self.current_page {
-            Page::Main => create_main_page(self.create_main_view_model()).into(),
-            Page::Settings => create_settings_page(self.create_settings_view_model()).into(),
+    fn on_save_exercises(&mut self) -> Task<Message> {
+        let workout = match self.workout_selection.clone() {
+            None => return self.on_close_exercise_editor(),
+            Some(w) => w,
         };
 
-        if let Some(topic) = self.show_confirmation.clone() {
-            let message = match topic {
-                ConfirmationTopic::WorkoutDeletion => self
-                    .workout_selection
-                    .clone()
-                    .map(|w| format!("Removing '{}' workout. Are you sure?", w.text)),
-                ConfirmationTopic::Clearance => {
-                    Some("Removing all workouts. Are you sure?".to_owned())
-                }
+        let pick_weight = match self.get_valid_pick_weight() {
+            None => return Task::none(),
+            Some(w) => w,
+        };
+
+        if let Some(position) = self.get_position(workout) {
+            let category = self.category_input.trim();
+            let after = Workout {
+                exercises: parse_exercises(&self.exercise_editor.text()),
+                pick_weight,
+                notes: self.notes_editor.text().trim_end().to_owned(),
+                tags: parse_tags(&self.tags_input),
+                category: (!category.is_empty()).then(|| category.to_owned()),
+                ..self.workouts[position].clone()
             };
-            let payload = ConfirmationPayload::new(topic, message);
-            modal(
-                page,
-                create_confirmation_dialog(payload.clone()),
-                Message::CloseConfirmationDialog(payload),
-            )
-        } else {
-            page
+            self.workout_selection = Some(after.clone());
+            self.execute_update(position, after);
         }
+
+        self.on_close_exercise_editor()
     }
 
-    fn create_main_view_model(&self) -> MainViewModel {
-        let workout = self
-            .workouts
-            .get(self.workout_index as usize)
-            .map_or("<empty>".to_owned(), |w| w.text.clone());
-        let total = self.workouts.len();
-        let has_next = total > 1;
-        let selected_number = if total == 0 {
-            0
-        } else {
-            self.workout_index + 1
-        };
+    fn on_close_exercise_editor(&mut self) -> Task<Message> {
+        self.exercise_editor = text_editor::Content::new();
+        self.pick_weight_input = String::new();
+        self.notes_editor = text_editor::Content::new();
+        self.category_input = String::new();
+        self.tags_input = String::new();
+        self.current_page = Page::Settings;
 
-        MainViewModel {
-            workout,
-            has_next,
-            selected_number,
-            total,
-        }
+        Task::none()
     }
 
-    fn create_settings_view_model(&self) -> SettingsViewModel {
-        SettingsViewModel {
-            workouts: self.workouts.clone(),
-            workout_selection: self.workout_selection.clone(),
-            workout_input: self.workout_input.clone(),
-            operation_flags: &self.operation_flags,
+    fn on_initiate_logging(&mut self, outcome: Outcome) -> Task<Message> {
+        if let Some(workout) = self.workouts.get(self.workout_index) {
+            let mut completion = CompletionInput::new(workout.id, outcome);
+            // the session stopwatch gives a sensible default for the duration
+            let minutes = self.timer.elapsed().as_secs() / 60;
+            if self.timer_mode == TimerMode::Stopwatch && minutes > 0 {
+                completion.duration = minutes.to_string();
+            }
+            self.completion = Some(completion);
         }
-    }
 
-    fn window_subscription(&self) -> Subscription<Message> {
-        event::listen_with(|event, _, _| match event {
-            Window(window::Event::Moved(p)) => Some(Message::WindowMoved(p.x, p.y)),
-            Window(window::Event::CloseRequested) => Some(Message::WindowCloseRequest),
-            _ => None,
-        })
+        Task::none()
     }
 
-    fn write_workouts_state(&mut self) {
-        let result = write_workouts_state(WorkoutsState {
-            index: self.workout_index,
-            workouts: self.workouts.iter().map(|w| w.text.clone()).collect(),
-        });
+    fn on_completion_note_input(&mut self, note: String) -> Task<Message> {
+        if let Some(completion) = self.completion.as_mut() {
+            completion.note = note;
+        }
 
-        if let Err(error) = result {
-            log_error(error.to_string()).ok();
+        Task::none()
+    }
+
+    fn on_completion_duration_input(&mut self, duration: String) -> Task<Message> {
+        if let Some(completion) = self.completion.as_mut() {
+            completion.duration = duration;
         }
+
+        Task::none()
     }
-}
 
-#[derive(Debug, Clone)]
-enum Message {
-    WindowId(Option<Id>),
-    NextWorkout,
-    OpenSettings,
-    CloseSettings,
-    CloseConfirmationDialog(ConfirmationPayload),
-    WorkoutSelection(Option<Workout>),
-    WorkoutInput(Option<String>),
-    AddWorkout,
-    UpdateWorkout,
-    InitiateWorkoutDeletion,
-    InitiateClearance,
-    MoveWorkoutUp,
-    MoveWorkoutDown,
-    WindowMoved(f32, f32),
-    WindowCloseRequest,
-}
+    fn on_close_completion_dialog(&mut self, confirmed: bool) -> Task<Message> {
+        let completion = match self.completion.take() {
+            Some(c) if confirmed && c.is_valid() => c,
+            _ => return Task::none(),
+        };
 
-#[derive(Debug, Clone)]
-struct Workout {
-    id: Uuid,
-    text: String,
-}
+        let position = match self.get_id_position(completion.workout_id) {
+            None => return Task::none(),
+            Some(p) => p,
+        };
 
-impl Workout {
-    fn new(text: String) -> Workout {
-        Workout {
-            id: Uuid::new_v4(),
-            text,
+        let entry = HistoryEntry::new(
+            &self.workouts[position],
+            Local::now(),
+            completion.outcome,
+            completion.note(),
+            completion.duration_secs(),
+        );
+        self.history.entries.push(entry);
+        self.write_history_state();
+        // the rotation only moves on if the schedule has not already moved it past the workout
+        if position == self.workout_index {
+            self.advance_workout(completion.outcome == Outcome::Completed);
         }
+
+        Task::none()
     }
-}
+
+    fn on_open_history(&mut self) -> Task<Message> {
+        self.current_page = Page::History;
+
+        Task::none()
+    }
+
+    fn on_close_history(&mut self) -> Task<Message> {
+        self.current_page = Page::Main;
+
+        Task::none()
+    }
+
+    fn on_open_stats(&mut self) -> Task<Message> {
+        self.current_page = Page::Stats;
+
+        Task::none()
+    }
+
+    fn on_close_stats(&mut self) -> Task<Message> {
+        self.current_page = Page::History;
+
+        Task::none()
+    }
+
+    fn on_stats_chart_selection(&mut self, chart: StatsChart) -> Task<Message> {
+        self.stats_chart = chart;
+
+        Task::none()
+    }
+
+    fn on_history_workout_filter(&mut self, option: WorkoutFilterOption) -> Task<Message> {
+        self.history_filter.workout_id = option.id;
+
+        Task::none()
+    }
+
+    fn on_history_from_input(&mut self, input: String) -> Task<Message> {
+        self.history_filter.from = input;
+
+        Task::none()
+    }
+
+    fn on_history_to_input(&mut self, input: String) -> Task<Message> {
+        self.history_filter.to = input;
+
+        Task::none()
+    }
+
+    fn on_switch_program(&mut self, option: ProgramOption) -> Task<Message> {
+        if option.id != self.program_id {
+            self.switch_program(option.id);
+            self.write_workouts_state();
+        }
+
+        Task::none()
+    }
+
+    fn on_program_input(&mut self, input: Option<String>) -> Task<Message> {
+        self.program_input = input;
+        self.update_operation_flags();
+
+        Task::none()
+    }
+
+    fn on_create_program(&mut self) -> Task<Message> {
+        let name = match self.get_valid_program_input() {
+            None => return Task::none(),
+            Some(s) => s,
+        };
+
+        self.add_program(Program::new(name));
+
+        Task::none()
+    }
+
+    fn on_duplicate_program(&mut self) -> Task<Message> {
+        let name = match self.get_valid_program_input() {
+            None => return Task::none(),
+            Some(s) => s,
+        };
+
+        self.sync_active_program();
+        if let Some(program) = self.get_active_program() {
+            self.add_program(program.duplicate(name));
+        }
+
+        Task::none()
+    }
+
+    fn on_rename_program(&mut self) -> Task<Message> {
+        let name = match self.get_valid_program_input() {
+            None => return Task::none(),
+            Some(s) => s,
+        };
+
+        let program_id = self.program_id;
+        if let Some(program) = self.programs.iter_mut().find(|p| p.id == program_id) {
+            program.name = name;
+            self.program_input = None;
+            self.update_operation_flags();
+            self.write_workouts_state();
+        }
+
+        Task::none()
+    }
+
+    fn on_initiate_program_deletion(&mut self) -> Task<Message> {
+        if self.programs.len() > 1 {
+            self.show_confirmation = Some(ConfirmationTopic::ProgramDeletion);
+        }
+
+        Task::none()
+    }
+
+    fn on_undo(&mut self) -> Task<Message> {
+        if self.is_editing_rotation()
+            && self.undo_history.undo(
+                &mut self.workouts,
+                &mut self.workout_index,
+                &mut self.settings,
+            )
+        {
+            self.reset_input();
+            self.update_operation_flags();
+            self.write_workouts_state();
+        }
+
+        Task::none()
+    }
+
+    fn on_redo(&mut self) -> Task<Message> {
+        if self.is_editing_rotation()
+            && self.undo_history.redo(
+                &mut self.workouts,
+                &mut self.workout_index,
+                &mut self.settings,
+            )
+        {
+            self.reset_input();
+            self.update_operation_flags();
+            self.write_workouts_state();
+        }
+
+        Task::none()
+    }
+
+    fn on_shortcut(&mut self, shortcut: Shortcut) -> Task<Message> {
+        if shortcut == Shortcut::Help || (self.show_help && shortcut == Shortcut::Back) {
+            return self.on_toggle_help();
+        }
+        if self.show_help {
+            return Task::none();
+        }
+
+        if let Some(topic) = self.show_confirmation.clone() {
+            let payload = ConfirmationPayload {
+                confirmed: shortcut == Shortcut::Confirm,
+                ..ConfirmationPayload::new(topic, None)
+            };
+            return match shortcut {
+                Shortcut::Confirm | Shortcut::Back => self.on_close_confirmation_dialog(payload),
+                _ => Task::none(),
+            };
+        }
+        if self.completion.is_some() {
+            return match shortcut {
+                Shortcut::Confirm => self.on_close_completion_dialog(true),
+                Shortcut::Back => self.on_close_completion_dialog(false),
+                _ => Task::none(),
+            };
+        }
+        if self.import.is_some() {
+            return match shortcut {
+                Shortcut::Confirm => self.on_close_import_dialog(true),
+                Shortcut::Back => self.on_close_import_dialog(false),
+                _ => Task::none(),
+            };
+        }
+
+        match (&self.current_page, shortcut) {
+            (Page::Main, Shortcut::Next) => self.on_initiate_logging(Outcome::Completed),
+            (Page::Main, Shortcut::Skip) if self.workouts.len() > 1 => {
+                self.on_initiate_logging(Outcome::Skipped)
+            }
+            (Page::Main, Shortcut::Previous) => self.on_previous_workout(),
+            (Page::Main, Shortcut::Settings) => self.on_open_settings(),
+            (Page::Main, Shortcut::History) => self.on_open_history(),
+            (Page::Main, Shortcut::Timer) => {
+                self.show_timer = true;
+                self.on_toggle_timer()
+            }
+            (Page::Settings, Shortcut::Back) => self.on_close_settings(),
+            (Page::Settings, Shortcut::Up) => self.select_adjacent_workout(-1),
+            (Page::Settings, Shortcut::Down) => self.select_adjacent_workout(1),
+            (Page::Settings, Shortcut::MoveUp) => self.on_move_workout_up(),
+            (Page::Settings, Shortcut::MoveDown) => self.on_move_workout_down(),
+            (Page::Settings, Shortcut::Delete) if self.workout_selection.is_some() => {
+                self.on_delete_workout()
+            }
+            (Page::Settings, Shortcut::Undo) => self.on_undo(),
+            (Page::Settings, Shortcut::Redo) => self.on_redo(),
+            (Page::History, Shortcut::Back) => self.on_close_history(),
+            (Page::Stats, Shortcut::Back) => self.on_close_stats(),
+            (Page::Transfer, Shortcut::Back) => self.on_close_transfer(),
+            (Page::Schedule, Shortcut::Back) => self.on_close_schedule(),
+            (Page::Session, Shortcut::Confirm) => self.on_log_set(),
+            (Page::Session, Shortcut::Skip) => self.on_skip_set(),
+            (Page::Session, Shortcut::Timer) => self.on_toggle_timer(),
+            (Page::Session, Shortcut::Back)
+                if self.session.as_ref().is_some_and(|s| s.logs.is_empty()) =>
+            {
+                self.on_cancel_session()
+            }
+            (Page::ExerciseEditor, Shortcut::Back) => self.on_close_exercise_editor(),
+            _ => Task::none(),
+        }
+    }
+
+    fn on_previous_workout(&mut self) -> Task<Message> {
+        let count = self.workouts.len();
+        if count > 1 {
+            self.set_workout_index((self.workout_index + count - 1) % count, false);
+        }
+
+        Task::none()
+    }
+
+    fn on_jump_to_workout(&mut self, option: WorkoutOption) -> Task<Message> {
+        if option.position < self.workouts.len() {
+            self.set_workout_index(option.position, false);
+        }
+
+        Task::none()
+    }
+
+    fn on_set_current_workout(&mut self) -> Task<Message> {
+        let position = match self
+            .workout_selection
+            .clone()
+            .and_then(|w| self.get_position(w))
+        {
+            None => return Task::none(),
+            Some(p) => p,
+        };
+
+        if position != self.workout_index {
+            self.execute(EditCommand::SetIndex {
+                before: self.workout_index,
+                after: position,
+            });
+        }
+
+        Task::none()
+    }
+
+    fn on_ordering_selection(&mut self, mode: OrderingMode) -> Task<Message> {
+        if mode != self.settings.ordering {
+            self.cycle.clear();
+            self.execute(EditCommand::SetSettings {
+                before: self.settings,
+                after: RotationSettings {
+                    ordering: mode,
+                    ..self.settings
+                },
+            });
+        }
+
+        Task::none()
+    }
+
+    fn on_allow_duplicates_toggle(&mut self, allow: bool) -> Task<Message> {
+        if allow != self.settings.allow_duplicates {
+            self.execute(EditCommand::SetSettings {
+                before: self.settings,
+                after: RotationSettings {
+                    allow_duplicates: allow,
+                    ..self.settings
+                },
+            });
+        }
+
+        Task::none()
+    }
+
+    /// Links the selection to an entry with the same text, or detaches it from its links.
+    fn on_toggle_link(&mut self) -> Task<Message> {
+        let position = match self
+            .workout_selection
+            .clone()
+            .and_then(|w| self.get_position(w))
+        {
+            None => return Task::none(),
+            Some(p) => p,
+        };
+
+        let after = if self.is_linked(position) {
+            unlink_workout(&self.workouts, position)
+        } else {
+            match link_workout(&self.workouts, position) {
+                None => return Task::none(),
+                Some(w) => w,
+            }
+        };
+        self.workout_selection = Some(after[position].clone());
+        self.execute(EditCommand::ReplaceAll {
+            before: (self.workouts.clone(), self.workout_index),
+            after: (after, self.workout_index),
+        });
+
+        Task::none()
+    }
+
+    fn on_open_schedule(&mut self) -> Task<Message> {
+        self.schedule_input = Some(ScheduleInput::new(&self.schedule, get_today()));
+        self.current_page = Page::Schedule;
+
+        Task::none()
+    }
+
+    fn on_close_schedule(&mut self) -> Task<Message> {
+        self.schedule_input = None;
+        self.current_page = Page::Settings;
+
+        Task::none()
+    }
+
+    fn on_save_schedule(&mut self) -> Task<Message> {
+        let input = match self.schedule_input.clone() {
+            None => return self.on_close_schedule(),
+            Some(i) => i,
+        };
+
+        if let Some(schedule) = input.to_schedule() {
+            self.schedule.schedule = schedule;
+            self.schedule.policy = input.policy;
+            // the current workout is due from today under the new schedule
+            self.schedule.set_due_from(get_today(), false);
+            self.write_workouts_state();
+        }
+
+        self.on_close_schedule()
+    }
+
+    fn on_schedule_input(&mut self, update: impl FnOnce(&mut ScheduleInput)) -> Task<Message> {
+        if let Some(input) = self.schedule_input.as_mut() {
+            update(input);
+        }
+
+        Task::none()
+    }
+
+    fn on_pick_weight_input(&mut self, pick_weight: String) -> Task<Message> {
+        self.pick_weight_input = pick_weight;
+
+        Task::none()
+    }
+
+    fn on_notes_editor_action(&mut self, action: text_editor::Action) -> Task<Message> {
+        self.notes_editor.perform(action);
+
+        Task::none()
+    }
+
+    fn on_category_input(&mut self, category: String) -> Task<Message> {
+        self.category_input = category;
+
+        Task::none()
+    }
+
+    fn on_tags_input(&mut self, tags: String) -> Task<Message> {
+        self.tags_input = tags;
+
+        Task::none()
+    }
+
+    fn on_rotation_filter_selection(&mut self, option: LabelFilterOption) -> Task<Message> {
+        if option.filter != self.rotation_filter {
+            self.rotation_filter = option.filter;
+            self.write_workouts_state();
+        }
+
+        Task::none()
+    }
+
+    fn on_settings_filter_selection(&mut self, option: LabelFilterOption) -> Task<Message> {
+        self.settings_filter = option.filter;
+        self.deselect_hidden_workouts();
+
+        Task::none()
+    }
+
+    fn on_search_input(&mut self, query: String) -> Task<Message> {
+        self.search_input = query;
+        self.deselect_hidden_workouts();
+
+        Task::none()
+    }
+
+    /// Selects the best match unless a shown workout is already selected.
+    fn on_search_submit(&mut self) -> Task<Message> {
+        if self.workout_selection.is_some() {
+            return Task::none();
+        }
+
+        let query = self.search_input.trim();
+        let best = self
+            .get_visible_positions()
+            .into_iter()
+            .filter_map(|p| search_workout(query, &self.workouts[p]).map(|m| (p, m.score)))
+            .max_by_key(|(p, score)| (*score, std::cmp::Reverse(*p)));
+        if let Some((position, _)) = best {
+            self.select_workout_at(position);
+        }
+
+        Task::none()
+    }
+
+    fn on_toggle_help(&mut self) -> Task<Message> {
+        self.show_help = !self.show_help;
+
+        Task::none()
+    }
+
+    fn on_toggle_timer_panel(&mut self) -> Task<Message> {
+        self.show_timer = !self.show_timer;
+
+        Task::none()
+    }
+
+    fn on_toggle_timer(&mut self) -> Task<Message> {
+        let now = Instant::now();
+        if self.timer.is_running() {
+            self.timer.pause(now);
+        } else if self.get_timer_amount().is_some() {
+            self.timer.start(now);
+        }
+
+        Task::none()
+    }
+
+    fn on_reset_timer(&mut self) -> Task<Message> {
+        self.timer.reset();
+
+        Task::none()
+    }
+
+    fn on_timer_mode_selection(&mut self, mode: TimerMode) -> Task<Message> {
+        self.timer_mode = mode;
+        self.timer_amount = mode
+            .get_amount_unit()
+            .map_or(String::new(), |_| mode.get_default_amount().to_string());
+        self.rebuild_timer();
+
+        Task::none()
+    }
+
+    fn on_timer_amount_input(&mut self, amount: String) -> Task<Message> {
+        self.timer_amount = amount;
+        self.rebuild_timer();
+
+        Task::none()
+    }
+
+    /// Transitions are cued visually by the timer panel.
+    fn on_timer_tick(&mut self, now: Instant) -> Task<Message> {
+        self.timer.tick(now);
+        if let Some(rest) = self.rest.as_mut() {
+            rest.tick(now);
+            if rest.is_finished() && !rest.is_cued() {
+                self.rest = None;
+            }
+        }
+
+        Task::none()
+    }
+
+    /// Catches the rotation up once the day has changed while the app is open.
+    fn on_date_check(&mut self, today: NaiveDate) -> Task<Message> {
+        if today != self.today {
+            self.apply_schedule(today);
+        }
+
+        Task::none()
+    }
+
+    fn on_start_session(&mut self) -> Task<Message> {
+        let (workout_id, exercises) = match self.workouts.get(self.workout_index) {
+            Some(w) if !w.exercises.is_empty() => (
+                w.id,
+                w.exercises
+                    .iter()
+                    .map(|e| {
+                        suggest(e, w.id, &self.history.entries).map_or(e.clone(), |s| s.apply(e))
+                    })
+                    .collect(),
+            ),
+            _ => return Task::none(),
+        };
+
+        let session = Session::new(workout_id, exercises, Local::now());
+        self.session_input = SessionInput::new(session.get_exercise());
+        self.session = Some(session);
+        self.current_page = Page::Session;
+
+        Task::none()
+    }
+
+    fn on_session_reps_input(&mut self, reps: String) -> Task<Message> {
+        self.session_input.reps = reps;
+
+        Task::none()
+    }
+
+    fn on_session_load_input(&mut self, load: String) -> Task<Message> {
+        self.session_input.load = load;
+
+        Task::none()
+    }
+
+    fn on_log_set(&mut self) -> Task<Message> {
+        let (session, reps, load) = match (
+            self.session.as_mut(),
+            self.session_input.get_reps(),
+            self.session_input.get_load(),
+        ) {
+            (Some(s), Some(reps), Some(load)) if !s.is_finished() => (s, reps, load),
+            _ => return Task::none(),
+        };
+
+        let has_next = session.log_set(reps, load);
+        self.session_input = SessionInput::new(session.get_exercise());
+        if has_next {
+            self.start_rest();
+        }
+
+        Task::none()
+    }
+
+    fn on_skip_set(&mut self) -> Task<Message> {
+        if let Some(session) = self.session.as_mut() {
+            session.skip_set();
+            self.session_input = SessionInput::new(session.get_exercise());
+        }
+
+        Task::none()
+    }
+
+    fn on_finish_session(&mut self) -> Task<Message> {
+        let session = match self.session.take() {
+            Some(s) if !s.logs.is_empty() => s,
+            other => {
+                self.session = other;
+                return Task::none();
+            }
+        };
+
+        // the rotation only moves on if the schedule has not already moved it past the workout
+        if let Some(position) = self.get_id_position(session.workout_id) {
+            let now = Local::now();
+            let mut entry = HistoryEntry::new(
+                &self.workouts[position],
+                now,
+                Outcome::Completed,
+                None,
+                session.get_duration_secs(now),
+            );
+            entry.sets = session.logs;
+            self.history.entries.push(entry);
+            self.write_history_state();
+            if position == self.workout_index {
+                self.advance_workout(true);
+            }
+        }
+
+        self.on_cancel_session()
+    }
+
+    fn on_cancel_session(&mut self) -> Task<Message> {
+        self.session = None;
+        self.rest = None;
+        self.session_input = SessionInput::default();
+        self.current_page = Page::Main;
+
+        Task::none()
+    }
+
+    /// Counts down the rest between sets, using the rest set on the timer panel if there is one.
+    fn start_rest(&mut self) {
+        let amount = match self.get_timer_amount() {
+            Some(amount) if self.timer_mode == TimerMode::Rest => amount,
+            _ => TimerMode::Rest.get_default_amount(),
+        };
+
+        let mut rest = Timer::new(TimerMode::Rest.get_phases(amount));
+        rest.start(Instant::now());
+        self.rest = Some(rest);
+    }
+
+    fn rebuild_timer(&mut self) {
+        if let Some(amount) = self.get_timer_amount() {
+            self.timer = Timer::new(self.timer_mode.get_phases(amount));
+        }
+    }
+
+    /// Modes without an amount always have a valid one.
+    fn get_timer_amount(&self) -> Option<u32> {
+        if self.timer_mode.get_amount_unit().is_none() {
+            return Some(0);
+        }
+
+        self.timer_amount
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|a| (1..=self.timer_mode.get_max_amount()).contains(a))
+    }
+
+    /// Selects the shown workout next to the selection, wrapping around the ends of the list.
+    fn select_adjacent_workout(&mut self, offset: isize) -> Task<Message> {
+        let visible = self.get_visible_positions();
+        let count = visible.len() as isize;
+        if count == 0 {
+            return Task::none();
+        }
+
+        let index = match self
+            .workout_selection
+            .clone()
+            .and_then(|w| self.get_position(w))
+            .and_then(|p| visible.iter().position(|v| *v == p))
+        {
+            Some(i) => (i as isize + offset).rem_euclid(count),
+            None if offset < 0 => count - 1,
+            None => 0,
+        };
+        self.select_workout_at(visible[index as usize]);
+
+        Task::none()
+    }
+
+    fn select_workout_at(&mut self, position: usize) {
+        let workout = self.workouts[position].clone();
+        self.workout_input = Some(workout.text.clone());
+        self.workout_selection = Some(workout);
+        self.update_operation_flags();
+    }
+
+    /// Positions of the workouts shown in the settings list by the label filter and search.
+    fn get_visible_positions(&self) -> Vec<usize> {
+        let query = self.search_input.trim();
+
+        self.workouts
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| self.settings_filter.as_ref().is_none_or(|f| f.matches(w)))
+            .filter(|(_, w)| query.is_empty() || search_workout(query, w).is_some())
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// Returns the shown workout above or below `position`, so moves skip hidden workouts.
+    fn get_adjacent_visible_position(&self, position: usize, up: bool) -> Option<usize> {
+        let visible = self.get_visible_positions();
+        if up {
+            visible.into_iter().rev().find(|p| *p < position)
+        } else {
+            visible.into_iter().find(|p| *p > position)
+        }
+    }
+
+    /// Operations act on the selection and the marks, so they must not stay out of sight.
+    fn deselect_hidden_workouts(&mut self) {
+        let visible = self.get_visible_positions();
+        let is_hidden = self
+            .workout_selection
+            .clone()
+            .and_then(|w| self.get_position(w))
+            .is_some_and(|p| !visible.contains(&p));
+        if is_hidden {
+            self.reset_input();
+        }
+        let visible_ids: Vec<Uuid> = visible.iter().map(|p| self.workouts[*p].id).collect();
+        self.marked.retain(|id| visible_ids.contains(id));
+        self.update_operation_flags();
+    }
+
+    fn on_open_transfer(&mut self) -> Task<Message> {
+        self.transfer.status = None;
+        self.current_page = Page::Transfer;
+
+        Task::none()
+    }
+
+    fn on_close_transfer(&mut self) -> Task<Message> {
+        self.current_page = Page::Settings;
+
+        Task::none()
+    }
+
+    fn on_transfer_path_input(&mut self, path: String) -> Task<Message> {
+        self.transfer.set_path(path);
+
+        Task::none()
+    }
+
+    fn on_transfer_format_selection(&mut self, format: TransferFormat) -> Task<Message> {
+        self.transfer.format = Some(format);
+
+        Task::none()
+    }
+
+    fn on_export_workouts(&mut self) -> Task<Message> {
+        match self.transfer.get_target() {
+            None => {}
+            Some((path, _)) if path.exists() => {
+                self.show_confirmation = Some(ConfirmationTopic::ExportOverwrite);
+            }
+            Some(_) => self.write_export(),
+        }
+
+        Task::none()
+    }
+
+    fn write_export(&mut self) {
+        let (path, format) = match self.transfer.get_target() {
+            None => return,
+            Some(t) => t,
+        };
+
+        let status = match export_workouts(&path, format, &self.workouts, self.workout_index) {
+            Ok(()) => format!(
+                "Exported {} workout(s) to {}",
+                self.workouts.len(),
+                path.display()
+            ),
+            Err(error) => format!("Export failed: {}", error),
+        };
+        self.transfer.status = Some(status);
+    }
+
+    fn on_preview_import(&mut self) -> Task<Message> {
+        let (path, format) = match self.transfer.get_target() {
+            None => return Task::none(),
+            Some(t) => t,
+        };
+
+        match import_workouts(
+            &path,
+            format,
+            &self.workouts,
+            self.settings.allow_duplicates,
+        ) {
+            Ok(preview) => {
+                self.transfer.status = None;
+                self.import = Some(ImportInput::new(preview));
+            }
+            Err(error) => self.transfer.status = Some(format!("Import failed: {}", error)),
+        }
+
+        Task::none()
+    }
+
+    fn on_merge_mode_selection(&mut self, mode: MergeMode) -> Task<Message> {
+        if let Some(import) = self.import.as_mut() {
+            import.mode = mode;
+        }
+
+        Task::none()
+    }
+
+    fn on_close_import_dialog(&mut self, confirmed: bool) -> Task<Message> {
+        let import = match self.import.take() {
+            Some(i) if confirmed => i,
+            _ => return Task::none(),
+        };
+
+        let (mut workouts, mut index) = (self.workouts.clone(), self.workout_index);
+        apply_import(&mut workouts, &mut index, import.preview, import.mode);
+        self.workout_selection = None;
+        self.execute(EditCommand::ReplaceAll {
+            before: (self.workouts.clone(), self.workout_index),
+            after: (workouts, index),
+        });
+        self.transfer.status = Some(format!(
+            "Imported, the list now has {} workout(s)",
+            self.workouts.len()
+        ));
+
+        Task::none()
+    }
+
+    fn add_program(&mut self, program: Program) {
+        let program_id = program.id;
+        self.sync_active_program();
+        self.programs.push(program);
+        self.switch_program(program_id);
+        self.write_workouts_state();
+    }
+
+    fn delete_program(&mut self) {
+        if self.programs.len() <= 1 {
+            return;
+        }
+
+        let program_id = self.program_id;
+        self.programs.retain(|p| p.id != program_id);
+        self.program_id = self.programs[0].id;
+        self.load_active_program();
+        self.write_workouts_state();
+    }
+
+    fn switch_program(&mut self, program_id: Uuid) {
+        if self.programs.iter().all(|p| p.id != program_id) {
+            return;
+        }
+
+        self.sync_active_program();
+        self.program_id = program_id;
+        self.load_active_program();
+        self.apply_schedule(get_today());
+    }
+
+    /// Copies the working rotation back into its program before the programs are read.
+    fn sync_active_program(&mut self) {
+        let program_id = self.program_id;
+        if let Some(program) = self.programs.iter_mut().find(|p| p.id == program_id) {
+            program.index = self.workout_index;
+            program.workouts = self.workouts.clone();
+            program.ordering = self.settings.ordering;
+            program.cycle = self.cycle.clone();
+            program.schedule = self.schedule.clone();
+            program.allow_duplicates = self.settings.allow_duplicates;
+            program.filter = self.rotation_filter.clone();
+        }
+    }
+
+    fn load_active_program(&mut self) {
+        let program = self.get_active_program().cloned().unwrap_or_default();
+        self.workout_index = program.index;
+        self.workouts = program.workouts;
+        self.settings = RotationSettings {
+            ordering: program.ordering,
+            allow_duplicates: program.allow_duplicates,
+        };
+        self.cycle = program.cycle;
+        self.schedule = program.schedule;
+        self.rotation_filter = program.filter;
+        self.settings_filter = None;
+        self.search_input = String::new();
+        self.reset_marks();
+        self.schedule_input = None;
+        self.undo_history.clear();
+        self.program_input = None;
+        self.reset_input();
+        self.update_operation_flags();
+    }
+
+    fn get_active_program(&self) -> Option<&Program> {
+        self.programs.iter().find(|p| p.id == self.program_id)
+    }
+
+    fn get_valid_pick_weight(&self) -> Option<u32> {
+        self.pick_weight_input.trim().parse::<u32>().ok()
+    }
+
+    fn get_valid_program_input(&self) -> Option<String> {
+        self.program_input
+            .clone()
+            .map(|s| s.trim().to_owned())
+            .filter(|s| !s.is_empty() && self.programs.iter().all(|p| !p.name.eq(s)))
+    }
+
+    /// Moves on to the next workout; a logged completion makes it due the next day.
+    fn advance_workout(&mut self, logged: bool) {
+        if !self.workouts.is_empty() {
+            let index = self.get_next_index();
+            self.set_workout_index(index, logged);
+        }
+    }
+
+    fn get_next_index(&mut self) -> usize {
+        get_next_index(
+            self.settings.ordering,
+            &self.workouts,
+            self.workout_index,
+            self.rotation_filter.as_ref(),
+            &mut self.cycle,
+            &self.history.entries,
+            &mut rand::rng(),
+        )
+    }
+
+    /// Moves the rotation on by the training days missed under the drop policy.
+    fn apply_schedule(&mut self, today: NaiveDate) {
+        self.today = today;
+        self.sync_active_program();
+        let program_id = self.program_id;
+        let program = match self.programs.iter_mut().find(|p| p.id == program_id) {
+            None => return,
+            Some(p) => p,
+        };
+        if !program.apply_schedule(today, &self.history.entries, &mut rand::rng()) {
+            return;
+        }
+
+        // catching up happens on its own, so it is not an edit that could be undone
+        self.workout_index = program.index;
+        self.cycle = program.cycle.clone();
+        self.schedule = program.schedule.clone();
+        self.write_workouts_state();
+    }
+
+    /// Moves the rotation outside of the settings page, which is not part of the undo history.
+    fn set_workout_index(&mut self, index: usize, logged: bool) {
+        self.schedule.set_due_from(get_today(), logged);
+        self.workout_index = index;
+        self.write_workouts_state();
+    }
+
+    fn load_workouts_state(&mut self, workouts_state: WorkoutsState) {
+        self.program_id = workouts_state.active_program;
+        self.programs = workouts_state.programs;
+        self.load_active_program();
+    }
+
+    fn start_recovery(&mut self, error: PersistenceError) {
+        log_error(error.to_string()).ok();
+
+        self.recovery = Some(Recovery {
+            error: error.to_string(),
+            backups: get_workouts_backups(),
+            can_reset_index: matches!(error, PersistenceError::IndexOutOfRange { .. }),
+        });
+        self.current_page = Page::Recovery;
+    }
+
+    fn finish_recovery(&mut self, result: Result<WorkoutsState, PersistenceError>) {
+        match result {
+            Ok(workouts_state) => {
+                self.load_workouts_state(workouts_state);
+                self.recovery = None;
+                self.current_page = Page::Main;
+            }
+            Err(error) => {
+                log_error(error.to_string()).ok();
+
+                if let Some(recovery) = self.recovery.as_mut() {
+                    recovery.error = error.to_string();
+                }
+            }
+        }
+    }
+
+    /// Moves the workouts at `positions` as one undoable step, keeping the active workout.
+    fn move_block(&mut self, positions: &[usize], to: usize) {
+        let mut workouts = self.workouts.clone();
+        let index = move_workouts(&mut workouts, self.workout_index, positions, to);
+        self.execute(EditCommand::ReplaceAll {
+            before: (self.workouts.clone(), self.workout_index),
+            after: (workouts, index),
+        });
+    }
+
+    fn execute(&mut self, command: EditCommand) {
+        self.undo_history.execute(
+            command,
+            &mut self.workouts,
+            &mut self.workout_index,
+            &mut self.settings,
+        );
+        self.update_operation_flags();
+        self.write_workouts_state();
+    }
+
+    /// Updates a workout together with the entries linked to it as one undoable step.
+    fn execute_update(&mut self, position: usize, after: Workout) {
+        if !self.is_linked(position) {
+            let before = self.workouts[position].clone();
+            self.execute(EditCommand::Update {
+                position,
+                before,
+                after,
+            });
+            return;
+        }
+
+        let mut workouts = self.workouts.clone();
+        update_linked_workouts(&mut workouts, position, after);
+        self.execute(EditCommand::ReplaceAll {
+            before: (self.workouts.clone(), self.workout_index),
+            after: (workouts, self.workout_index),
+        });
+    }
+
+    fn is_linked(&self, position: usize) -> bool {
+        self.workouts
+            .iter()
+            .any(|w| w.is_linked_to(&self.workouts[position]))
+    }
+
+    fn has_valid_input(&self) -> bool {
+        self.workout_input.as_ref().is_some_and(|s| {
+            is_valid_workout_text(s, &self.workouts, self.settings.allow_duplicates)
+        })
+    }
+
+    fn get_valid_input(&mut self) -> Option<String> {
+        self.workout_input
+            .clone()
+            .filter(|s| is_valid_workout_text(s, &self.workouts, self.settings.allow_duplicates))
+    }
+
+    fn reset_input(&mut self) {
+        self.workout_selection = None;
+        self.workout_input = None;
+    }
+
+    fn reset_marks(&mut self) {
+        self.marked.clear();
+        self.dragged = None;
+        self.drag_target = None;
+    }
+
+    fn update_operation_flags(&mut self) {
+        self.operation_flags
+            .set(OperationFlags::CanAdd, self.has_valid_input());
+        self.operation_flags.set(
+            OperationFlags::CanUpdate,
+            self.workout_selection.is_some() && self.has_valid_input(),
+        );
+        self.operation_flags
+            .set(OperationFlags::CanDelete, self.workout_selection.is_some());
+        self.operation_flags.set(
+            OperationFlags::CanEditExercises,
+            self.workout_selection.is_some(),
+        );
+        self.operation_flags
+            .set(OperationFlags::CanClear, !self.workouts.is_empty());
+        self.operation_flags.set(
+            OperationFlags::CanNameProgram,
+            self.get_valid_program_input().is_some(),
+        );
+        self.operation_flags
+            .set(OperationFlags::CanDeleteProgram, self.programs.len() > 1);
+        self.operation_flags
+            .set(OperationFlags::CanUndo, self.undo_history.can_undo());
+        self.operation_flags
+            .set(OperationFlags::CanRedo, self.undo_history.can_redo());
+        self.operation_flags.set(
+            OperationFlags::CanSetCurrent,
+            self.workout_selection
+                .clone()
+                .and_then(|w| self.get_position(w))
+                .is_some_and(|p| p != self.workout_index),
+        );
+        self.operation_flags.set(
+            OperationFlags::CanLink,
+            self.workout_selection
+                .clone()
+                .and_then(|w| self.get_position(w))
+                .is_some_and(|p| self.is_linked(p) || link_workout(&self.workouts, p).is_some()),
+        );
+        self.operation_flags.set(
+            OperationFlags::CanMoveUp,
+            self.workout_selection
+                .clone()
+                .and_then(|w| self.get_position(w))
+                .and_then(|p| self.get_adjacent_visible_position(p, true))
+                .is_some(),
+        );
+        self.operation_flags.set(
+            OperationFlags::CanMoveDown,
+            self.workout_selection
+                .clone()
+                .and_then(|w| self.get_position(w))
+                .and_then(|p| self.get_adjacent_visible_position(p, false))
+                .is_some(),
+        );
+        let positions = self.get_moving_positions();
+        self.operation_flags.set(
+            OperationFlags::CanMoveToTop,
+            positions.iter().enumerate().any(|(i, p)| i != *p),
+        );
+        self.operation_flags.set(
+            OperationFlags::CanMoveToBottom,
+            positions
+                .iter()
+                .rev()
+                .enumerate()
+                .any(|(i, p)| self.workouts.len() - 1 - i != *p),
+        );
+    }
+
+    /// Undo and redo only apply while the settings page is shown without a dialog on top.
+    fn is_editing_rotation(&self) -> bool {
+        matches!(self.current_page, Page::Settings) && self.show_confirmation.is_none()
+    }
+
+    fn get_position(&self, workout: Workout) -> Option<usize> {
+        self.get_id_position(workout.id)
+    }
+
+    fn get_id_position(&self, id: Uuid) -> Option<usize> {
+        self.workouts.iter().position(|w| w.id == id)
+    }
+
+    fn get_marked_positions(&self) -> Vec<usize> {
+        self.workouts
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| self.marked.contains(&w.id))
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// The marked workouts, or the selected one when nothing is marked.
+    fn get_moving_positions(&self) -> Vec<usize> {
+        let marked = self.get_marked_positions();
+        if !marked.is_empty() {
+            return marked;
+        }
+
+        self.workout_selection
+            .clone()
+            .and_then(|w| self.get_position(w))
+            .into_iter()
+            .collect()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let page = match self.current_page {
+            Page::Main => create_main_page(self.create_main_view_model()).into(),
+            Page::Settings => create_settings_page(self.create_settings_view_model()).into(),
+            Page::Recovery => create_recovery_page(self.create_recovery_view_model()).into(),
+            Page::History => create_history_page(self.create_history_view_model()).into(),
+            Page::Transfer => create_transfer_page(self.create_transfer_view_model()).into(),
+            Page::Schedule => create_schedule_page(self.create_schedule_view_model()).into(),
+            Page::Session => create_session_page(self.create_session_view_model()).into(),
+            Page::Stats => create_stats_page(self.create_stats_view_model()).into(),
+            Page::ExerciseEditor => {
+                create_exercise_editor_page(self.create_exercise_editor_view_model()).into()
+            }
+        };
+
+        if self.show_help {
+            return modal(page, create_help_overlay(), Message::ToggleHelp);
+        }
+
+        if let Some(import) = self.import.clone() {
+            return modal(
+                page,
+                create_import_dialog(import),
+                Message::CloseImportDialog(false),
+            );
+        }
+
+        if let Some(completion) = self.completion.clone() {
+            let workout = self
+                .get_id_position(completion.workout_id)
+                .map_or(String::new(), |p| self.workouts[p].text.clone());
+
+            return modal(
+                page,
+                create_completion_dialog(workout, completion),
+                Message::CloseCompletionDialog(false),
+            );
+        }
+
+        if let Some(topic) = self.show_confirmation.clone() {
+            let message = match topic {
+                ConfirmationTopic::ProgramDeletion => self.get_active_program().map(|p| {
+                    format!(
+                        "Removing '{}' program with all its workouts. Are you sure?",
+                        p.name
+                    )
+                }),
+                ConfirmationTopic::ExportOverwrite => self
+                    .transfer
+                    .get_target()
+                    .map(|(path, _)| format!("'{}' already exists. Overwrite it?", path.display())),
+            };
+            let payload = ConfirmationPayload::new(topic, message);
+            modal(
+                page,
+                create_confirmation_dialog(payload.clone()),
+                Message::CloseConfirmationDialog(payload),
+            )
+        } else {
+            page
+        }
+    }
+
+    fn create_main_view_model(&self) -> MainViewModel {
+        let current = self.workouts.get(self.workout_index);
+        let workout = current.map_or("<empty>".to_owned(), |w| w.text.clone());
+        let exercises = current.map_or(vec![], |w| {
+            w.exercises
+                .iter()
+                .map(|e| ExerciseLine {
+                    name: e.name.clone(),
+                    targets: e.describe_targets(),
+                    suggestion: suggest(e, w.id, &self.history.entries).map(|s| s.to_string()),
+                })
+                .collect()
+        });
+        let total = self.workouts.len();
+        let has_next = total > 1;
+        let has_current = current.is_some();
+        let has_exercises = current.is_some_and(|w| !w.exercises.is_empty());
+        let labels = current.and_then(|w| w.describe_labels());
+        let notes = current.map(|w| w.notes.clone()).filter(|n| !n.is_empty());
+        let selected_number = if total == 0 {
+            0
+        } else {
+            self.workout_index + 1
+        };
+
+        let programs: Vec<_> = self
+            .programs
+            .iter()
+            .map(|p| ProgramOption {
+                id: p.id,
+                name: p.name.clone(),
+            })
+            .collect();
+        let program = programs.iter().find(|p| p.id == self.program_id).cloned();
+        let workout_options = self
+            .workouts
+            .iter()
+            .enumerate()
+            .map(|(position, w)| WorkoutOption {
+                position,
+                text: w.text.clone(),
+            })
+            .collect();
+        let schedule_note =
+            self.schedule
+                .is_enabled()
+                .then(|| match self.schedule.get_day(get_today()) {
+                    Day::Training => String::from("Today's workout"),
+                    Day::Rest { next: Some(next) } => {
+                        format!("Rest day, next training {}", format_day(next))
+                    }
+                    Day::Rest { next: None } => String::from("Rest day"),
+                });
+
+        MainViewModel {
+            programs,
+            program,
+            workout,
+            exercises,
+            has_next,
+            has_current,
+            has_exercises,
+            selected_number,
+            total,
+            workout_options,
+            filter_options: get_label_filter_options(&self.workouts),
+            filter: LabelFilterOption {
+                filter: self.rotation_filter.clone(),
+            },
+            labels,
+            notes,
+            schedule_note,
+            timer: self.show_timer.then(|| self.create_timer_view_model()),
+        }
+    }
+
+    fn create_session_view_model(&self) -> SessionViewModel {
+        let session = self.session.as_ref();
+        let exercise = session.and_then(|s| s.get_exercise());
+        let step = session.and_then(|s| s.get_step());
+        let (step_number, steps) = session.map_or((0, 0), |s| s.get_progress());
+
+        SessionViewModel {
+            workout: session
+                .and_then(|s| self.get_id_position(s.workout_id))
+                .map_or(String::new(), |p| self.workouts[p].text.clone()),
+            exercise: exercise.map(|e| e.name.clone()),
+            targets: exercise.map_or(String::new(), |e| e.describe_targets()),
+            set: step.map_or(0, |s| s.set),
+            sets: exercise.map_or(0, |e| e.sets.unwrap_or(1).max(1)),
+            step: step_number,
+            steps,
+            input: self.session_input.clone(),
+            logged: session.map_or(String::new(), |s| describe_sets(&s.logs)),
+            can_finish: session.is_some_and(|s| !s.logs.is_empty()),
+            rest: self.rest.as_ref().map(|r| r.get_status()),
+            timer: self.create_timer_view_model(),
+        }
+    }
+
+    fn create_timer_view_model(&self) -> TimerViewModel {
+        TimerViewModel {
+            mode: self.timer_mode,
+            amount: self.timer_amount.clone(),
+            is_amount_valid: self.get_timer_amount().is_some(),
+            status: self.timer.get_status(),
+            is_running: self.timer.is_running(),
+            is_cued: self.timer.is_cued(),
+        }
+    }
+
+    fn create_settings_view_model(&self) -> SettingsViewModel<'_> {
+        let occurrences = get_occurrences(&self.workouts);
+        let query = self.search_input.trim();
+        let items = self
+            .get_visible_positions()
+            .into_iter()
+            .map(|p| {
+                let workout = &self.workouts[p];
+                ListItem {
+                    workout: workout.clone(),
+                    occurrence: occurrences[p],
+                    is_linked: self.is_linked(p),
+                    highlights: search_workout(query, workout).map_or(vec![], |m| m.positions),
+                }
+            })
+            .collect();
+
+        SettingsViewModel {
+            items,
+            skip_counts: count_skips(&self.history.entries),
+            search_input: self.search_input.clone(),
+            is_selection_linked: self
+                .workout_selection
+                .clone()
+                .and_then(|w| self.get_position(w))
+                .is_some_and(|p| self.is_linked(p)),
+            filter_options: get_label_filter_options(&self.workouts),
+            filter: LabelFilterOption {
+                filter: self.settings_filter.clone(),
+            },
+            ordering: self.settings.ordering,
+            allow_duplicates: self.settings.allow_duplicates,
+            current_id: self.workouts.get(self.workout_index).map(|w| w.id),
+            marked: self.marked.clone(),
+            drag_target: self.dragged.and(self.drag_target),
+            workout_selection: self.workout_selection.clone(),
+            workout_input: self.workout_input.clone(),
+            program_input: self.program_input.clone(),
+            operation_flags: &self.operation_flags,
+        }
+    }
+
+    fn create_recovery_view_model(&self) -> RecoveryViewModel {
+        self.recovery
+            .clone()
+            .map_or(RecoveryViewModel::default(), |recovery| RecoveryViewModel {
+                error: recovery.error,
+                backups: recovery.backups,
+                can_reset_index: recovery.can_reset_index,
+            })
+    }
+
+    fn create_exercise_editor_view_model(&self) -> ExerciseEditorViewModel<'_> {
+        ExerciseEditorViewModel {
+            workout: self
+                .workout_selection
+                .clone()
+                .map_or(String::new(), |w| w.text),
+            content: &self.exercise_editor,
+            notes: &self.notes_editor,
+            category: self.category_input.clone(),
+            tags: self.tags_input.clone(),
+            pick_weight: self.pick_weight_input.clone(),
+            is_pick_weight_valid: self.get_valid_pick_weight().is_some(),
+        }
+    }
+
+    fn create_schedule_view_model(&self) -> ScheduleViewModel {
+        ScheduleViewModel {
+            input: self
+                .schedule_input
+                .clone()
+                .unwrap_or_else(|| ScheduleInput::new(&self.schedule, get_today())),
+        }
+    }
+
+    fn create_transfer_view_model(&self) -> TransferViewModel {
+        TransferViewModel {
+            program: self
+                .get_active_program()
+                .map_or(String::new(), |p| p.name.clone()),
+            input: self.transfer.clone(),
+        }
+    }
+
+    fn create_stats_view_model(&self) -> StatsViewModel {
+        let entries = &self.history.entries;
+        let today = get_today();
+        let points = match self.stats_chart {
+            StatsChart::SessionsPerWeek => count_sessions_per_week(entries, today, STATS_WEEKS)
+                .into_iter()
+                .map(|w| ChartPoint {
+                    label: w.start.format("%b %-d").to_string(),
+                    value: w.count as f32,
+                })
+                .collect(),
+            StatsChart::CompletionRate => get_workout_rates(entries, &self.workouts)
+                .into_iter()
+                .map(|r| ChartPoint {
+                    value: r.get_rate() * 100.0,
+                    label: r.workout_text.chars().take(STATS_LABEL_CHARS).collect(),
+                })
+                .collect(),
+            StatsChart::Volume | StatsChart::TopLoad => {
+                let trend = get_volume_trend(entries);
+                let skipped = trend.len().saturating_sub(STATS_DAYS);
+                trend
+                    .into_iter()
+                    .skip(skipped)
+                    .map(|p| ChartPoint {
+                        label: p.date.format("%b %-d").to_string(),
+                        value: if self.stats_chart == StatsChart::Volume {
+                            p.volume
+                        } else {
+                            p.top_load
+                        },
+                    })
+                    .collect()
+            }
+        };
+
+        StatsViewModel {
+            streaks: get_streaks(entries, &self.schedule.schedule, today),
+            completed: entries
+                .iter()
+                .filter(|e| e.outcome == Outcome::Completed)
+                .count(),
+            chart: self.stats_chart,
+            points,
+        }
+    }
+
+    fn create_history_view_model(&self) -> HistoryViewModel {
+        let filter = self.history_filter.to_filter();
+        let entries = filter_entries(&self.history.entries, &filter)
+            .into_iter()
+            .cloned()
+            .collect();
+
+        let all_option = WorkoutFilterOption {
+            id: None,
+            text: "All workouts".to_owned(),
+        };
+        let mut workout_options = vec![all_option.clone()];
+        let other_programs = self.programs.iter().filter(|p| p.id != self.program_id);
+        let known_workouts = self
+            .workouts
+            .iter()
+            .chain(other_programs.flat_map(|p| p.workouts.iter()))
+            .map(|w| (w.id, w.text.clone()))
+            .chain(
+                self.history
+                    .entries
+                    .iter()
+                    .map(|e| (e.workout_id, e.workout_text.clone())),
+            );
+        for (id, text) in known_workouts {
+            if workout_options.iter().all(|o| o.id != Some(id)) {
+                workout_options.push(WorkoutFilterOption { id: Some(id), text });
+            }
+        }
+        let workout_selection = workout_options
+            .iter()
+            .find(|o| o.id == filter.workout_id)
+            .cloned()
+            .or(Some(all_option));
+
+        HistoryViewModel {
+            entries,
+            workout_options,
+            workout_selection,
+            from_input: self.history_filter.from.clone(),
+            to_input: self.history_filter.to.clone(),
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch([
+            self.window_subscription(),
+            self.keyboard_subscription(),
+            self.timer_subscription(),
+            self.drag_subscription(),
+            time::every(DATE_CHECK_INTERVAL).map(|_| Message::DateCheck(get_today())),
+        ])
+    }
+
+    /// Ends a drag on any release, also outside of the list.
+    fn drag_subscription(&self) -> Subscription<Message> {
+        if self.dragged.is_none() {
+            return Subscription::none();
+        }
+
+        event::listen_with(|event, _, _| match event {
+            Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => Some(Message::DropWorkout),
+            _ => None,
+        })
+    }
+
+    /// Ticks while a timer runs or a cue is still shown, also when the window is hidden.
+    fn timer_subscription(&self) -> Subscription<Message> {
+        if self.timer.is_running() || self.timer.is_cued() || self.rest.is_some() {
+            time::every(TIMER_TICK_INTERVAL).map(Message::TimerTick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn keyboard_subscription(&self) -> Subscription<Message> {
+        keyboard::on_key_press(|key, modifiers| get_shortcut(key, modifiers).map(Message::Shortcut))
+    }
+
+    fn window_subscription(&self) -> Subscription<Message> {
+        event::listen_with(|event, _, _| match event {
+            Window(window::Event::Moved(p)) => Some(Message::WindowMoved(p.x, p.y)),
+            Window(window::Event::CloseRequested) => Some(Message::WindowCloseRequest),
+            _ => None,
+        })
+    }
+
+    fn write_workouts_state(&mut self) {
+        self.sync_active_program();
+        let result =
+            write_workouts_state(WorkoutsState::new(self.program_id, self.programs.clone()));
+
+        if let Err(error) = result {
+            log_error(error.to_string()).ok();
+        }
+    }
+
+    fn write_history_state(&self) {
+        if let Err(error) = write_history_state(&self.history) {
+            log_error(error.to_string()).ok();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    WindowId(Option<Id>),
+    OpenSettings,
+    CloseSettings,
+    CloseConfirmationDialog(ConfirmationPayload),
+    WorkoutSelection(Option<Workout>),
+    WorkoutInput(Option<String>),
+    AddWorkout,
+    UpdateWorkout,
+    DeleteWorkout,
+    ClearWorkouts,
+    MoveWorkoutUp,
+    MoveWorkoutDown,
+    MoveWorkoutsToTop,
+    MoveWorkoutsToBottom,
+    MarkWorkout(Uuid, bool),
+    DragWorkout(Uuid),
+    DragOverWorkout(Uuid),
+    DragOutWorkout(Uuid),
+    DropWorkout,
+    WindowMoved(f32, f32),
+    WindowCloseRequest,
+    RestoreBackup(PathBuf),
+    ResetIndex,
+    StartFresh,
+    OpenExerciseEditor,
+    ExerciseEditorAction(text_editor::Action),
+    SaveExercises,
+    CloseExerciseEditor,
+    InitiateCompletion,
+    InitiateSkip,
+    CompletionNoteInput(String),
+    CompletionDurationInput(String),
+    CloseCompletionDialog(bool),
+    OpenHistory,
+    CloseHistory,
+    OpenStats,
+    CloseStats,
+    StatsChartSelection(StatsChart),
+    HistoryWorkoutFilter(WorkoutFilterOption),
+    HistoryFromInput(String),
+    HistoryToInput(String),
+    SwitchProgram(ProgramOption),
+    ProgramInput(Option<String>),
+    CreateProgram,
+    DuplicateProgram,
+    RenameProgram,
+    InitiateProgramDeletion,
+    OpenTransfer,
+    CloseTransfer,
+    TransferPathInput(String),
+    TransferFormatSelection(TransferFormat),
+    ExportWorkouts,
+    PreviewImport,
+    MergeModeSelection(MergeMode),
+    CloseImportDialog(bool),
+    Undo,
+    Redo,
+    Shortcut(Shortcut),
+    PreviousWorkout,
+    JumpToWorkout(WorkoutOption),
+    SetCurrentWorkout,
+    OrderingSelection(OrderingMode),
+    AllowDuplicatesToggle(bool),
+    ToggleLink,
+    StartSession,
+    SessionRepsInput(String),
+    SessionLoadInput(String),
+    LogSet,
+    SkipSet,
+    FinishSession,
+    CancelSession,
+    ToggleTimerPanel,
+    ToggleTimer,
+    ResetTimer,
+    TimerModeSelection(TimerMode),
+    TimerAmountInput(String),
+    TimerTick(Instant),
+    DateCheck(NaiveDate),
+    OpenSchedule,
+    CloseSchedule,
+    SaveSchedule,
+    ScheduleModeSelection(ScheduleMode),
+    ScheduleDayToggle(Weekday, bool),
+    ScheduleOnInput(String),
+    ScheduleOffInput(String),
+    ScheduleStartInput(String),
+    CatchUpPolicySelection(CatchUpPolicy),
+    PickWeightInput(String),
+    NotesEditorAction(text_editor::Action),
+    CategoryInput(String),
+    TagsInput(String),
+    RotationFilterSelection(LabelFilterOption),
+    SettingsFilterSelection(LabelFilterOption),
+    SearchInput(String),
+    SearchSubmit,
+    ToggleHelp,
+}
+
+#[derive(Debug, Clone, Default)]
+struct HistoryFilterInput {
+    workout_id: Option<Uuid>,
+    from: String,
+    to: String,
+}
+
+impl HistoryFilterInput {
+    fn to_filter(&self) -> HistoryFilter {
+        HistoryFilter {
+            workout_id: self.workout_id,
+            from: parse_date(&self.from),
+            to: parse_date(&self.to),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Recovery {
+    error: String,
+    backups: Vec<PathBuf>,
+    can_reset_index: bool,
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::{AppState, Workout};
+    use crate::history::Outcome;
+    use crate::ordering::OrderingMode;
+    use crate::schedule::{CatchUpPolicy, Schedule, ScheduleSettings};
+    use crate::shortcut::Shortcut;
+    use crate::timer::TimerMode;
+    use crate::ui::completion_dialog::CompletionInput;
+    use crate::ui::confirmation_dialog::ConfirmationTopic;
+    use crate::ui::{LabelFilterOption, Page, ProgramOption, WorkoutOption};
+    use crate::undo::RotationSettings;
+    use crate::workout::{LabelFilter, Program, Workout, parse_exercises};
+    use crate::{AppState, OperationFlags, get_data_dir_arg};
+    use chrono::{NaiveDate, Weekday};
+    use iced::widget::text_editor;
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_has_valid_input_given_unique_input_should_be_true() {
+        let state = AppState {
+            workouts: vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+            workout_input: Some(String::from("workout3")),
+            ..AppState::default()
+        };
+
+        assert!(state.has_valid_input())
+    }
+
+    #[test]
+    fn test_has_valid_input_given_existing_input_should_be_false() {
+        let state = AppState {
+            workouts: vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+            workout_input: Some(String::from("workout2")),
+            ..AppState::default()
+        };
+
+        assert!(!state.has_valid_input())
+    }
+
+    #[test]
+    fn test_get_valid_input_given_valid_input_should_return_input() {
+        let mut state = AppState {
+            workouts: vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout3")),
+            ],
+            workout_input: Some(String::from("workout4")),
+            ..AppState::default()
+        };
+
+        assert_eq!(Some(String::from("workout4")), state.get_valid_input())
+    }
+
+    #[test]
+    fn test_get_valid_input_given_empty_input_should_return_none() {
+        let mut state = AppState {
+            workouts: vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+            workout_input: Some(String::from("")),
+            ..AppState::default()
+        };
+
+        assert_eq!(None, state.get_valid_input())
+    }
+
+    #[test]
+    fn test_get_valid_input_given_existing_input_should_return_none() {
+        let mut state = AppState {
+            workouts: vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+            workout_input: Some(String::from("workout1")),
+            ..AppState::default()
+        };
+
+        assert_eq!(None, state.get_valid_input())
+    }
+
+    #[test]
+    fn test_on_update_workout_should_keep_id() {
+        let workout = Workout::new(String::from("workout1"));
+        let mut state = AppState {
+            workouts: vec![workout.clone()],
+            workout_selection: Some(workout.clone()),
+            workout_input: Some(String::from("workout2")),
+            ..AppState::default()
+        };
+
+        let _ = state.on_update_workout();
+
+        assert_eq!(workout.id, state.workouts[0].id);
+        assert_eq!("workout2", state.workouts[0].text)
+    }
+
+    #[test]
+    fn test_has_valid_input_given_existing_input_and_duplicates_allowed_should_be_true() {
+        let state = AppState {
+            workouts: vec![Workout::new(String::from("workout1"))],
+            workout_input: Some(String::from("workout1")),
+            settings: RotationSettings {
+                allow_duplicates: true,
+                ..RotationSettings::default()
+            },
+            ..AppState::default()
+        };
+
+        assert!(state.has_valid_input())
+    }
+
+    #[test]
+    fn test_on_add_workout_given_duplicates_allowed_should_add_repeated_entry() {
+        let mut state = AppState {
+            workouts: vec![
+                Workout::new(String::from("A")),
+                Workout::new(String::from("B")),
+            ],
+            workout_input: Some(String::from("A")),
+            settings: RotationSettings {
+                allow_duplicates: true,
+                ..RotationSettings::default()
+            },
+            ..AppState::default()
+        };
+
+        let _ = state.on_add_workout();
+
+        assert_eq!(3, state.workouts.len());
+        assert_ne!(state.workouts[0].id, state.workouts[2].id)
+    }
+
+    #[test]
+    fn test_on_update_workout_given_linked_entry_should_propagate_and_undo_together() {
+        let workout = Workout::new(String::from("A"));
+        let mut state = AppState {
+            workouts: vec![
+                workout.clone(),
+                Workout::new(String::from("B")),
+                Workout::new(String::from("A")),
+            ],
+            workout_selection: Some(workout.clone()),
+            settings: RotationSettings {
+                allow_duplicates: true,
+                ..RotationSettings::default()
+            },
+            current_page: Page::Settings,
+            ..AppState::default()
+        };
+        let _ = state.on_toggle_link();
+        let _ = state.on_workout_input(Some(String::from("C")));
+
+        let _ = state.on_update_workout();
+        assert_eq!("C", state.workouts[2].text);
+        let _ = state.on_undo();
+
+        assert_eq!("A", state.workouts[0].text);
+        assert_eq!("A", state.workouts[2].text)
+    }
+
+    #[test]
+    fn test_on_toggle_link_given_linked_entry_should_unlink() {
+        let workout = Workout::new(String::from("A"));
+        let mut state = AppState {
+            workouts: vec![workout.clone(), Workout::new(String::from("A"))],
+            workout_selection: Some(workout),
+            ..AppState::default()
+        };
+        let _ = state.on_toggle_link();
+        assert!(state.is_linked(0));
+
+        let _ = state.on_toggle_link();
+
+        assert!(!state.is_linked(0))
+    }
+
+    #[test]
+    fn test_on_move_workout_down_should_keep_ids() {
+        let first = Workout::new(String::from("workout1"));
+        let second = Workout::new(String::from("workout2"));
+        let mut state = AppState {
+            workouts: vec![first.clone(), second.clone()],
+            workout_selection: Some(first.clone()),
+            ..AppState::default()
+        };
+
+        let _ = state.on_move_workout_down();
+
+        assert_eq!(
+            vec![second.id, first.id],
+            state.workouts.iter().map(|w| w.id).collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn test_on_undo_given_cleared_workouts_should_restore_them() {
+        let workouts = vec![
+            Workout::new(String::from("workout1")),
+            Workout::new(String::from("workout2")),
+        ];
+        let mut state = AppState {
+            workouts: workouts.clone(),
+            workout_index: 1,
+            current_page: Page::Settings,
+            ..AppState::default()
+        };
+
+        let _ = state.on_clear_workouts();
+        let _ = state.on_undo();
+
+        assert_eq!(workouts.len(), state.workouts.len());
+        assert_eq!(1, state.workout_index);
+        assert!(state.operation_flags.contains(OperationFlags::CanRedo))
+    }
+
+    #[test]
+    fn test_on_undo_given_changed_settings_should_restore_them() {
+        let mut state = AppState {
+            current_page: Page::Settings,
+            ..AppState::default()
+        };
+
+        let _ = state.on_ordering_selection(OrderingMode::Shuffle);
+        let _ = state.on_allow_duplicates_toggle(true);
+        let _ = state.on_undo();
+        assert_eq!(OrderingMode::Shuffle, state.settings.ordering);
+        assert!(!state.settings.allow_duplicates);
+        let _ = state.on_undo();
+
+        assert_eq!(RotationSettings::default(), state.settings)
+    }
+
+    #[test]
+    fn test_on_undo_given_main_page_should_keep_workouts() {
+        let mut state = AppState {
+            workout_input: Some(String::from("workout1")),
+            ..AppState::default()
+        };
+
+        let _ = state.on_add_workout();
+        let _ = state.on_undo();
+
+        assert_eq!(1, state.workouts.len())
+    }
+
+    #[test]
+    fn test_on_shortcut_given_previous_on_first_workout_should_wrap() {
+        let mut state = AppState {
+            workouts: vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+            ..AppState::default()
+        };
+
+        let _ = state.on_shortcut(Shortcut::Previous);
+
+        assert_eq!(1, state.workout_index)
+    }
+
+    fn create_state(index: usize, texts: &[&str]) -> AppState {
+        AppState {
+            workout_index: index,
+            workouts: texts.iter().map(|t| Workout::new(t.to_string())).collect(),
+            ..AppState::default()
+        }
+    }
+
+    #[test]
+    fn test_on_previous_workout_given_single_workout_should_keep_index() {
+        let mut state = create_state(0, &["workout1"]);
+
+        let _ = state.on_previous_workout();
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_previous_workout_given_last_workout_should_decrement() {
+        let mut state = create_state(2, &["workout1", "workout2", "workout3"]);
+
+        let _ = state.on_previous_workout();
+
+        assert_eq!(1, state.workout_index)
+    }
+
+    #[test]
+    fn test_advance_workout_given_last_workout_should_wrap() {
+        let mut state = create_state(2, &["workout1", "workout2", "workout3"]);
+
+        state.advance_workout(true);
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_advance_workout_given_rotation_filter_should_skip_other_workouts() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3", "workout4"]);
+        state.workouts[0].tags = vec![String::from("upper")];
+        state.workouts[3].tags = vec![String::from("upper")];
+        state.rotation_filter = Some(LabelFilter::Tag(String::from("upper")));
+
+        state.advance_workout(true);
+        assert_eq!(3, state.workout_index);
+        state.advance_workout(true);
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_advance_workout_given_filter_without_matches_should_advance_normally() {
+        let mut state = create_state(0, &["workout1", "workout2"]);
+        state.rotation_filter = Some(LabelFilter::Category(String::from("cardio")));
+
+        state.advance_workout(true);
+
+        assert_eq!(1, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_save_exercises_should_store_notes_tags_and_category() {
+        let mut state = create_state(0, &["workout1"]);
+        state.workout_selection = Some(state.workouts[0].clone());
+        let _ = state.on_open_exercise_editor();
+        state.notes_editor = text_editor::Content::with_text("Keep rests short");
+        let _ = state.on_category_input(String::from(" Strength "));
+        let _ = state.on_tags_input(String::from("upper, push"));
+
+        let _ = state.on_save_exercises();
+
+        assert_eq!("Keep rests short", state.workouts[0].notes);
+        assert_eq!(vec!["upper", "push"], state.workouts[0].tags);
+        assert_eq!(Some(String::from("Strength")), state.workouts[0].category)
+    }
+
+    #[test]
+    fn test_create_settings_view_model_given_filter_should_list_matching_workouts() {
+        let mut state = create_state(0, &["workout1", "workout2"]);
+        state.workouts[1].category = Some(String::from("Cardio"));
+        let _ = state.on_settings_filter_selection(LabelFilterOption {
+            filter: Some(LabelFilter::Category(String::from("Cardio"))),
+        });
+
+        let view_model = state.create_settings_view_model();
+
+        assert_eq!(1, view_model.items.len());
+        assert_eq!("workout2", view_model.items[0].workout.text)
+    }
+
+    #[test]
+    fn test_create_settings_view_model_given_search_should_highlight_matches() {
+        let mut state = create_state(0, &["Upper Day", "Lower Day", "Legs"]);
+        let _ = state.on_search_input(String::from("upd"));
+
+        let view_model = state.create_settings_view_model();
+
+        assert_eq!(1, view_model.items.len());
+        assert_eq!(vec![0, 1, 6], view_model.items[0].highlights)
+    }
+
+    #[test]
+    fn test_on_search_input_given_selection_hidden_should_deselect() {
+        let mut state = create_state(0, &["Push", "Pull"]);
+        state.workout_selection = Some(state.workouts[0].clone());
+
+        let _ = state.on_search_input(String::from("pul"));
+
+        assert!(state.workout_selection.is_none());
+        assert!(!state.operation_flags.contains(OperationFlags::CanDelete))
+    }
 
     #[test]
-    fn test_has_unique_input_given_unique_input_should_be_true() {
-        let state = AppState {
-            workouts: vec![
-                Workout::new(String::from("workout1")),
-                Workout::new(String::from("workout2")),
-            ],
-            workout_input: Some(String::from("workout3")),
+    fn test_on_search_submit_should_select_best_match() {
+        let mut state = create_state(0, &["Pxxush", "Push", "Legs"]);
+        let _ = state.on_search_input(String::from("push"));
+
+        let _ = state.on_search_submit();
+
+        assert_eq!(
+            Some(state.workouts[1].id),
+            state.workout_selection.map(|w| w.id)
+        )
+    }
+
+    #[test]
+    fn test_select_adjacent_workout_given_search_should_select_among_results() {
+        let mut state = create_state(0, &["Push A", "Legs", "Push B"]);
+        let _ = state.on_search_input(String::from("push"));
+
+        let _ = state.select_adjacent_workout(1);
+        let _ = state.select_adjacent_workout(1);
+
+        assert_eq!(
+            Some(state.workouts[2].id),
+            state.workout_selection.map(|w| w.id)
+        )
+    }
+
+    #[test]
+    fn test_on_move_workout_up_given_search_should_pass_hidden_workouts() {
+        let mut state = create_state(0, &["Push A", "Legs", "Push B"]);
+        let _ = state.on_search_input(String::from("push"));
+        let _ = state.on_workout_selection(Some(state.workouts[2].clone()));
+
+        let _ = state.on_move_workout_up();
+
+        assert_eq!("Push B", state.workouts[0].text);
+        assert!(!state.operation_flags.contains(OperationFlags::CanMoveUp))
+    }
+
+    fn get_texts(state: &AppState) -> Vec<&str> {
+        state.workouts.iter().map(|w| w.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_on_move_workout_down_given_active_workout_should_follow_it() {
+        let mut state = create_state(0, &["A", "B", "C"]);
+        let _ = state.on_workout_selection(Some(state.workouts[0].clone()));
+
+        let _ = state.on_move_workout_down();
+
+        assert_eq!(1, state.workout_index);
+        assert_eq!("A", state.workouts[state.workout_index].text)
+    }
+
+    #[test]
+    fn test_on_move_workouts_to_bottom_given_selection_should_keep_active_workout() {
+        let mut state = create_state(2, &["A", "B", "C", "D"]);
+        let _ = state.on_workout_selection(Some(state.workouts[0].clone()));
+
+        let _ = state.on_move_workouts_to_bottom();
+
+        assert_eq!(vec!["B", "C", "D", "A"], get_texts(&state));
+        assert_eq!("C", state.workouts[state.workout_index].text);
+        assert!(
+            !state
+                .operation_flags
+                .contains(OperationFlags::CanMoveToBottom)
+        )
+    }
+
+    #[test]
+    fn test_on_move_workouts_to_top_given_marked_workouts_should_move_them_together() {
+        let mut state = create_state(0, &["A", "B", "C", "D"]);
+        let _ = state.on_mark_workout(state.workouts[3].id, true);
+        let _ = state.on_mark_workout(state.workouts[1].id, true);
+
+        let _ = state.on_move_workouts_to_top();
+
+        assert_eq!(vec!["B", "D", "A", "C"], get_texts(&state));
+        assert_eq!(2, state.workout_index);
+        assert!(!state.operation_flags.contains(OperationFlags::CanMoveToTop))
+    }
+
+    #[test]
+    fn test_on_search_input_given_marked_workout_hidden_should_unmark_it() {
+        let mut state = create_state(0, &["Push A", "Legs", "Push B"]);
+        let _ = state.on_mark_workout(state.workouts[1].id, true);
+        let _ = state.on_mark_workout(state.workouts[2].id, true);
+        let _ = state.on_search_input(String::from("push"));
+
+        let _ = state.on_move_workouts_to_top();
+
+        assert_eq!(vec!["Push B", "Push A", "Legs"], get_texts(&state))
+    }
+
+    #[test]
+    fn test_on_drop_workout_given_drag_downwards_should_place_after_target() {
+        let mut state = create_state(1, &["A", "B", "C", "D"]);
+        let (dragged, target) = (state.workouts[0].id, state.workouts[2].id);
+
+        let _ = state.on_drag_workout(dragged);
+        let _ = state.on_drag_over_workout(target);
+        let _ = state.on_drop_workout();
+
+        assert_eq!(vec!["B", "C", "A", "D"], get_texts(&state));
+        assert_eq!("B", state.workouts[state.workout_index].text);
+        assert_eq!(None, state.dragged)
+    }
+
+    #[test]
+    fn test_on_drop_workout_given_marked_workouts_dragged_upwards_should_place_before_target() {
+        let mut state = create_state(0, &["A", "B", "C", "D", "E"]);
+        let _ = state.on_mark_workout(state.workouts[2].id, true);
+        let _ = state.on_mark_workout(state.workouts[4].id, true);
+        let (dragged, target) = (state.workouts[4].id, state.workouts[1].id);
+
+        let _ = state.on_drag_workout(dragged);
+        let _ = state.on_drag_over_workout(target);
+        let _ = state.on_drop_workout();
+
+        assert_eq!(vec!["A", "C", "E", "B", "D"], get_texts(&state))
+    }
+
+    #[test]
+    fn test_on_drop_workout_given_release_outside_rows_should_end_drag() {
+        let mut state = create_state(0, &["A", "B"]);
+        let _ = state.on_drag_workout(state.workouts[0].id);
+        let _ = state.on_drag_over_workout(state.workouts[1].id);
+        let _ = state.on_drag_out_workout(state.workouts[1].id);
+
+        let _ = state.on_drop_workout();
+        let _ = state.on_drag_over_workout(state.workouts[1].id);
+        let _ = state.on_drop_workout();
+
+        assert_eq!(vec!["A", "B"], get_texts(&state));
+        assert_eq!(None, state.dragged)
+    }
+
+    #[test]
+    fn test_on_undo_given_completed_workout_should_keep_index() {
+        let mut state = create_state(0, &["workout1", "workout2"]);
+        state.current_page = Page::Settings;
+        let _ = state.on_workout_input(Some(String::from("workout3")));
+        let _ = state.on_add_workout();
+
+        state.advance_workout(true);
+        let _ = state.on_undo();
+
+        assert_eq!(2, state.workouts.len());
+        assert_eq!(1, state.workout_index)
+    }
+
+    #[test]
+    fn test_advance_workout_should_keep_redo() {
+        let mut state = create_state(0, &["workout1", "workout2"]);
+        state.current_page = Page::Settings;
+        let _ = state.on_workout_input(Some(String::from("workout3")));
+        let _ = state.on_add_workout();
+        let _ = state.on_undo();
+
+        state.advance_workout(true);
+
+        assert!(state.operation_flags.contains(OperationFlags::CanRedo))
+    }
+
+    #[test]
+    fn test_advance_workout_given_large_rotation_should_pass_every_workout() {
+        let texts: Vec<String> = (0..300).map(|i| format!("workout{}", i)).collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let mut state = create_state(0, &texts);
+
+        for expected in 1..300 {
+            state.advance_workout(true);
+            assert_eq!(expected, state.workout_index);
+        }
+        state.advance_workout(true);
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_previous_workout_given_first_of_large_rotation_should_wrap_to_last() {
+        let texts: Vec<String> = (0..300).map(|i| format!("workout{}", i)).collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let mut state = create_state(0, &texts);
+
+        let _ = state.on_previous_workout();
+
+        assert_eq!(299, state.workout_index)
+    }
+
+    #[test]
+    fn test_apply_schedule_given_drop_policy_should_advance_by_missed_days() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        let monday = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
+        state.schedule = ScheduleSettings {
+            schedule: Schedule::Weekdays {
+                days: vec![Weekday::Mon, Weekday::Wed],
+            },
+            policy: CatchUpPolicy::Drop,
+            due_from: Some(monday),
+        };
+
+        state.apply_schedule(NaiveDate::from_ymd_opt(2025, 9, 5).unwrap());
+
+        assert_eq!(2, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_initiate_logging_given_due_schedule_should_keep_index() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        state.schedule = ScheduleSettings {
+            schedule: Schedule::Weekdays {
+                days: vec![Weekday::Mon, Weekday::Wed],
+            },
+            policy: CatchUpPolicy::Drop,
+            due_from: NaiveDate::from_ymd_opt(2025, 9, 1),
+        };
+
+        let _ = state.on_initiate_logging(Outcome::Completed);
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_date_check_given_new_day_should_apply_schedule() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        state.today = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
+        state.schedule = ScheduleSettings {
+            schedule: Schedule::Weekdays {
+                days: vec![Weekday::Mon, Weekday::Wed],
+            },
+            policy: CatchUpPolicy::Drop,
+            due_from: NaiveDate::from_ymd_opt(2025, 9, 1),
+        };
+
+        let _ = state.on_date_check(NaiveDate::from_ymd_opt(2025, 9, 5).unwrap());
+
+        assert_eq!(2, state.workout_index);
+        assert_eq!(NaiveDate::from_ymd_opt(2025, 9, 5), Some(state.today))
+    }
+
+    #[test]
+    fn test_on_date_check_given_same_day_should_keep_index() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        let today = NaiveDate::from_ymd_opt(2025, 9, 5).unwrap();
+        state.today = today;
+        state.schedule = ScheduleSettings {
+            schedule: Schedule::Weekdays {
+                days: vec![Weekday::Mon, Weekday::Wed],
+            },
+            policy: CatchUpPolicy::Drop,
+            due_from: NaiveDate::from_ymd_opt(2025, 9, 1),
+        };
+
+        let _ = state.on_date_check(today);
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_date_check_given_new_day_should_not_be_undoable() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        state.current_page = Page::Settings;
+        state.today = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
+        state.schedule = ScheduleSettings {
+            schedule: Schedule::Weekdays {
+                days: vec![Weekday::Mon, Weekday::Wed],
+            },
+            policy: CatchUpPolicy::Drop,
+            due_from: NaiveDate::from_ymd_opt(2025, 9, 1),
+        };
+
+        let _ = state.on_date_check(NaiveDate::from_ymd_opt(2025, 9, 5).unwrap());
+        let _ = state.on_undo();
+
+        assert_eq!(2, state.workout_index)
+    }
+
+    #[test]
+    fn test_apply_schedule_given_catch_up_policy_should_keep_index() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        let monday = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
+        state.schedule = ScheduleSettings {
+            schedule: Schedule::Weekdays {
+                days: vec![Weekday::Mon, Weekday::Wed],
+            },
+            policy: CatchUpPolicy::CatchUp,
+            due_from: Some(monday),
+        };
+
+        state.apply_schedule(NaiveDate::from_ymd_opt(2025, 9, 5).unwrap());
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_timer_mode_selection_given_rest_should_use_default_amount() {
+        let mut state = AppState::default();
+
+        let _ = state.on_timer_mode_selection(TimerMode::Rest);
+
+        assert_eq!("90", state.timer_amount);
+        assert_eq!(
+            Some(Duration::from_secs(90)),
+            state.timer.get_status().remaining
+        )
+    }
+
+    #[test]
+    fn test_on_toggle_timer_given_invalid_amount_should_not_start() {
+        let mut state = AppState::default();
+        let _ = state.on_timer_mode_selection(TimerMode::Emom);
+        let _ = state.on_timer_amount_input(String::from("0"));
+
+        let _ = state.on_toggle_timer();
+
+        assert!(!state.timer.is_running())
+    }
+
+    #[test]
+    fn test_get_timer_amount_given_emom_rounds_above_cap_should_be_none() {
+        let mut state = AppState::default();
+        let _ = state.on_timer_mode_selection(TimerMode::Emom);
+
+        let _ = state.on_timer_amount_input(String::from("99"));
+        assert_eq!(Some(99), state.get_timer_amount());
+        let _ = state.on_timer_amount_input(String::from("400000000"));
+
+        assert_eq!(None, state.get_timer_amount());
+        assert_eq!(99, state.timer.get_status().rounds)
+    }
+
+    fn create_session_state() -> AppState {
+        let mut state = create_state(0, &["workout1", "workout2"]);
+        state.workouts[0].exercises = parse_exercises("Squat 2x5 @100kg\nPlank 60s");
+        let _ = state.on_start_session();
+        state
+    }
+
+    #[test]
+    fn test_on_log_set_given_more_sets_should_start_rest() {
+        let mut state = create_session_state();
+
+        let _ = state.on_log_set();
+
+        assert!(state.rest.as_ref().is_some_and(|r| r.is_running()))
+    }
+
+    #[test]
+    fn test_on_log_set_given_running_stopwatch_should_keep_it() {
+        let mut state = create_session_state();
+        let _ = state.on_toggle_timer();
+
+        let _ = state.on_log_set();
+
+        assert!(state.timer.is_running());
+        assert_eq!(TimerMode::Stopwatch, state.timer_mode)
+    }
+
+    #[test]
+    fn test_on_finish_session_should_record_sets_and_advance() {
+        let mut state = create_session_state();
+        let _ = state.on_log_set();
+        let _ = state.on_session_reps_input(String::from("4"));
+        let _ = state.on_log_set();
+
+        let _ = state.on_finish_session();
+
+        let entry = state.history.entries.last().unwrap();
+        assert_eq!(
+            vec![Some(5), Some(4)],
+            entry.sets.iter().map(|s| s.reps).collect::<Vec<_>>()
+        );
+        assert_eq!(1, state.workout_index);
+        assert!(matches!(state.current_page, Page::Main))
+    }
+
+    #[test]
+    fn test_on_finish_session_given_moved_rotation_should_log_started_workout() {
+        let mut state = create_session_state();
+        let _ = state.on_log_set();
+        state.workout_index = 1;
+
+        let _ = state.on_finish_session();
+
+        let entry = state.history.entries.last().unwrap();
+        assert_eq!(state.workouts[0].id, entry.workout_id);
+        assert_eq!(1, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_jump_to_workout_given_last_position_should_set_index() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+
+        let _ = state.on_jump_to_workout(WorkoutOption {
+            position: 2,
+            text: String::from("workout3"),
+        });
+
+        assert_eq!(2, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_jump_to_workout_given_position_out_of_range_should_keep_index() {
+        let mut state = create_state(1, &["workout1", "workout2"]);
+
+        let _ = state.on_jump_to_workout(WorkoutOption {
+            position: 2,
+            text: String::from("workout3"),
+        });
+
+        assert_eq!(1, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_set_current_workout_should_be_undoable() {
+        let mut state = create_state(0, &["workout1", "workout2"]);
+        state.current_page = Page::Settings;
+        state.workout_selection = Some(state.workouts[1].clone());
+
+        let _ = state.on_set_current_workout();
+        assert_eq!(1, state.workout_index);
+        let _ = state.on_undo();
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_delete_workout_given_current_last_workout_should_move_index_back() {
+        let mut state = create_state(1, &["workout1", "workout2"]);
+        state.workout_selection = Some(state.workouts[1].clone());
+
+        let _ = state.on_delete_workout();
+
+        assert_eq!(0, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_shortcut_given_up_without_selection_should_select_last() {
+        let workouts = vec![
+            Workout::new(String::from("workout1")),
+            Workout::new(String::from("workout2")),
+        ];
+        let mut state = AppState {
+            workouts: workouts.clone(),
+            current_page: Page::Settings,
             ..AppState::default()
         };
 
-        assert!(state.has_unique_input())
+        let _ = state.on_shortcut(Shortcut::Up);
+
+        assert_eq!(Some(workouts[1].id), state.workout_selection.map(|w| w.id))
     }
 
     #[test]
-    fn test_has_unique_input_given_existing_input_should_be_false() {
-        let state = AppState {
-            workouts: vec![
-                Workout::new(String::from("workout1")),
-                Workout::new(String::from("workout2")),
-            ],
-            workout_input: Some(String::from("workout2")),
+    fn test_on_export_workouts_given_existing_file_should_confirm_before_overwriting() {
+        let path = std::env::temp_dir().join(format!("workout-iterator-{}.txt", Uuid::new_v4()));
+        std::fs::write(&path, "old").unwrap();
+        let mut state = create_state(0, &["workout1"]);
+        state.transfer.set_path(path.display().to_string());
+
+        let _ = state.on_export_workouts();
+        assert!(matches!(
+            state.show_confirmation,
+            Some(ConfirmationTopic::ExportOverwrite)
+        ));
+        assert_eq!("old", std::fs::read_to_string(&path).unwrap());
+        let _ = state.on_shortcut(Shortcut::Confirm);
+
+        assert!(std::fs::read_to_string(&path).unwrap().contains("workout1"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_on_shortcut_given_confirm_in_confirmation_dialog_should_delete() {
+        let mut state = AppState {
+            programs: vec![Program::default(), Program::default()],
+            current_page: Page::Settings,
+            show_confirmation: Some(ConfirmationTopic::ProgramDeletion),
             ..AppState::default()
         };
+        state.program_id = state.programs[0].id;
 
-        assert!(!state.has_unique_input())
+        let _ = state.on_shortcut(Shortcut::Confirm);
+
+        assert_eq!(1, state.programs.len());
+        assert!(state.show_confirmation.is_none())
     }
 
     #[test]
-    fn test_get_valid_input_given_valid_input_should_return_input() {
+    fn test_on_shortcut_given_delete_should_remove_without_confirmation() {
+        let workout = Workout::new(String::from("workout1"));
         let mut state = AppState {
-            workouts: vec![
-                Workout::new(String::from("workout1")),
-                Workout::new(String::from("workout3")),
-            ],
-            workout_input: Some(String::from("workout4")),
+            workouts: vec![workout.clone()],
+            workout_selection: Some(workout),
+            current_page: Page::Settings,
             ..AppState::default()
         };
 
-        assert_eq!(Some(String::from("workout4")), state.get_valid_input())
+        let _ = state.on_shortcut(Shortcut::Delete);
+        assert!(state.workouts.is_empty());
+        assert!(state.show_confirmation.is_none());
+        let _ = state.on_undo();
+
+        assert_eq!(1, state.workouts.len())
     }
 
     #[test]
-    fn test_get_valid_input_given_empty_input_should_return_none() {
+    fn test_on_close_completion_dialog_given_moved_rotation_should_log_opened_workout() {
+        let mut state = create_state(0, &["workout1", "workout2", "workout3"]);
+        let _ = state.on_initiate_logging(Outcome::Completed);
+        state.workout_index = 2;
+
+        let _ = state.on_close_completion_dialog(true);
+
+        assert_eq!(state.workouts[0].id, state.history.entries[0].workout_id);
+        assert_eq!(2, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_close_completion_dialog_given_confirmed_should_record_entry_and_advance() {
+        let workout = Workout::new(String::from("workout1"));
         let mut state = AppState {
-            workouts: vec![
-                Workout::new(String::from("workout1")),
-                Workout::new(String::from("workout2")),
-            ],
-            workout_input: Some(String::from("")),
+            workouts: vec![workout.clone(), Workout::new(String::from("workout2"))],
+            completion: Some(CompletionInput {
+                workout_id: workout.id,
+                note: String::from("felt strong"),
+                duration: String::from("40"),
+                ..CompletionInput::default()
+            }),
             ..AppState::default()
         };
 
-        assert_eq!(None, state.get_valid_input())
+        let _ = state.on_close_completion_dialog(true);
+
+        assert_eq!(1, state.history.entries.len());
+        assert_eq!(workout.id, state.history.entries[0].workout_id);
+        assert_eq!(Some(2400), state.history.entries[0].duration_secs);
+        assert_eq!(1, state.workout_index);
+        assert!(state.completion.is_none())
     }
 
     #[test]
-    fn test_get_valid_input_given_existing_input_should_return_none() {
+    fn test_on_close_completion_dialog_given_cancelled_should_not_record_entry() {
+        let mut state = AppState {
+            workouts: vec![Workout::new(String::from("workout1"))],
+            completion: Some(CompletionInput::default()),
+            ..AppState::default()
+        };
+
+        let _ = state.on_close_completion_dialog(false);
+
+        assert!(state.history.entries.is_empty());
+        assert!(state.completion.is_none())
+    }
+
+    #[test]
+    fn test_on_switch_program_should_keep_each_rotation() {
         let mut state = AppState {
             workouts: vec![
                 Workout::new(String::from("workout1")),
                 Workout::new(String::from("workout2")),
             ],
-            workout_input: Some(String::from("workout1")),
+            workout_index: 1,
+            program_input: Some(String::from("Travel")),
             ..AppState::default()
         };
+        let default_program = ProgramOption {
+            id: state.program_id,
+            name: String::from("Default"),
+        };
 
-        assert_eq!(None, state.get_valid_input())
+        let _ = state.on_create_program();
+
+        assert_eq!(2, state.programs.len());
+        assert!(state.workouts.is_empty());
+        assert_eq!(0, state.workout_index);
+
+        let _ = state.on_switch_program(default_program);
+
+        assert_eq!(2, state.workouts.len());
+        assert_eq!(1, state.workout_index)
+    }
+
+    #[test]
+    fn test_on_duplicate_program_should_copy_workouts_with_new_ids() {
+        let workout = Workout::new(String::from("workout1"));
+        let mut state = AppState {
+            workouts: vec![workout.clone()],
+            program_input: Some(String::from("Copy")),
+            ..AppState::default()
+        };
+        let original_id = state.program_id;
+
+        let _ = state.on_duplicate_program();
+
+        assert_ne!(original_id, state.program_id);
+        assert_eq!("workout1", state.workouts[0].text);
+        assert_ne!(workout.id, state.workouts[0].id)
+    }
+
+    #[test]
+    fn test_get_valid_program_input_given_existing_name_should_return_none() {
+        let state = AppState {
+            program_input: Some(String::from("Default")),
+            ..AppState::default()
+        };
+
+        assert_eq!(None, state.get_valid_program_input())
+    }
+
+    #[test]
+    fn test_delete_program_given_last_program_should_keep_it() {
+        let mut state = AppState::default();
+
+        state.delete_program();
+
+        assert_eq!(1, state.programs.len())
+    }
+
+    #[test]
+    fn test_delete_program_should_activate_remaining_program() {
+        let remaining = Program {
+            workouts: vec![Workout::new(String::from("workout1"))],
+            ..Program::new(String::from("Remaining"))
+        };
+        let mut state = AppState::default();
+        state.programs.insert(0, remaining.clone());
+
+        state.delete_program();
+
+        assert_eq!(remaining.id, state.program_id);
+        assert_eq!(1, state.workouts.len())
+    }
+
+    #[test]
+    fn test_get_data_dir_arg_given_separate_value_should_return_path() {
+        let args = ["--data-dir", "/tmp/workouts"]
+            .map(String::from)
+            .into_iter();
+
+        assert_eq!(Some(PathBuf::from("/tmp/workouts")), get_data_dir_arg(args))
+    }
+
+    #[test]
+    fn test_get_data_dir_arg_given_inline_value_should_return_path() {
+        let args = ["--data-dir=/tmp/workouts"].map(String::from).into_iter();
+
+        assert_eq!(Some(PathBuf::from("/tmp/workouts")), get_data_dir_arg(args))
+    }
+
+    #[test]
+    fn test_get_data_dir_arg_given_no_arg_should_return_none() {
+        let args = ["--other"].map(String::from).into_iter();
+
+        assert_eq!(None, get_data_dir_arg(args))
     }
 }
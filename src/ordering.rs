@@ -0,0 +1,422 @@
+use crate::history::{HistoryEntry, Outcome};
+use crate::workout::{LabelFilter, Workout};
+use chrono::{DateTime, Local};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderingMode {
+    #[default]
+    Sequential,
+    Shuffle,
+    Weighted,
+    LeastRecent,
+}
+
+impl OrderingMode {
+    pub const ALL: [OrderingMode; 4] = [
+        OrderingMode::Sequential,
+        OrderingMode::Shuffle,
+        OrderingMode::Weighted,
+        OrderingMode::LeastRecent,
+    ];
+
+    pub fn strategy(&self) -> Box<dyn OrderingStrategy> {
+        match self {
+            OrderingMode::Sequential => Box::new(Sequential),
+            OrderingMode::Shuffle => Box::new(Shuffle),
+            OrderingMode::Weighted => Box::new(Weighted),
+            OrderingMode::LeastRecent => Box::new(LeastRecent),
+        }
+    }
+}
+
+impl Display for OrderingMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingMode::Sequential => write!(f, "Sequential"),
+            OrderingMode::Shuffle => write!(f, "Shuffled"),
+            OrderingMode::Weighted => write!(f, "Weighted"),
+            OrderingMode::LeastRecent => write!(f, "Least recent"),
+        }
+    }
+}
+
+/// What a strategy may look at to pick the workout after the current one.
+pub struct Rotation<'a> {
+    pub workouts: &'a [Workout],
+    pub index: usize,
+    /// Limits the candidates to matching workouts.
+    pub filter: Option<&'a LabelFilter>,
+    /// Ids of the workouts already done in the current shuffle cycle, including the current one.
+    pub cycle: &'a [Uuid],
+    pub history: &'a [HistoryEntry],
+}
+
+impl Rotation<'_> {
+    /// Positions of all workouts but the current one that pass the filter, starting after it.
+    fn get_candidates(&self) -> Vec<usize> {
+        let count = self.workouts.len();
+        (1..count)
+            .map(|i| (self.index + i) % count)
+            .filter(|p| self.is_candidate(&self.workouts[*p]))
+            .collect()
+    }
+
+    fn is_candidate(&self, workout: &Workout) -> bool {
+        self.filter.is_none_or(|f| f.matches(workout))
+    }
+}
+
+pub trait OrderingStrategy {
+    /// Returns the position of the next workout; only called when there are candidates.
+    fn next_index(&self, rotation: &Rotation, rng: &mut dyn RngCore) -> usize;
+}
+
+pub struct Sequential;
+
+impl OrderingStrategy for Sequential {
+    fn next_index(&self, rotation: &Rotation, _: &mut dyn RngCore) -> usize {
+        rotation.get_candidates()[0]
+    }
+}
+
+/// Visits every workout once per cycle in random order.
+pub struct Shuffle;
+
+impl OrderingStrategy for Shuffle {
+    fn next_index(&self, rotation: &Rotation, rng: &mut dyn RngCore) -> usize {
+        let candidates = rotation.get_candidates();
+        let remaining: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|p| !rotation.cycle.contains(&rotation.workouts[*p].id))
+            .collect();
+        let pool = if remaining.is_empty() {
+            &candidates
+        } else {
+            &remaining
+        };
+
+        pool[rng.random_range(0..pool.len())]
+    }
+}
+
+/// Picks randomly with a probability proportional to the workout pick weight.
+pub struct Weighted;
+
+impl OrderingStrategy for Weighted {
+    fn next_index(&self, rotation: &Rotation, rng: &mut dyn RngCore) -> usize {
+        let candidates = rotation.get_candidates();
+        let total: u64 = candidates
+            .iter()
+            .map(|p| rotation.workouts[*p].pick_weight as u64)
+            .sum();
+        if total == 0 {
+            return Sequential.next_index(rotation, rng);
+        }
+
+        let mut roll = rng.random_range(0..total);
+        for position in candidates {
+            let weight = rotation.workouts[position].pick_weight as u64;
+            if roll < weight {
+                return position;
+            }
+            roll -= weight;
+        }
+
+        unreachable!("roll is below the sum of weights")
+    }
+}
+
+/// Picks the workout whose last completion is the oldest, never completed ones first.
+pub struct LeastRecent;
+
+impl OrderingStrategy for LeastRecent {
+    fn next_index(&self, rotation: &Rotation, _: &mut dyn RngCore) -> usize {
+        rotation
+            .get_candidates()
+            .into_iter()
+            .min_by_key(|p| get_last_completion(rotation.history, rotation.workouts[*p].id))
+            .unwrap_or(0)
+    }
+}
+
+/// Advances the rotation with the given mode, keeping track of the shuffle cycle. The filter
+/// passes over workouts that do not match it, unless none of them match.
+pub fn get_next_index(
+    mode: OrderingMode,
+    workouts: &[Workout],
+    index: usize,
+    filter: Option<&LabelFilter>,
+    cycle: &mut Vec<Uuid>,
+    history: &[HistoryEntry],
+    rng: &mut dyn RngCore,
+) -> usize {
+    if workouts.len() < 2 {
+        cycle.clear();
+        return 0;
+    }
+
+    if let Some(current) = workouts.get(index)
+        && !cycle.contains(&current.id)
+    {
+        cycle.push(current.id);
+    }
+    cycle.retain(|id| workouts.iter().any(|w| w.id == *id));
+
+    let filter = filter.filter(|f| workouts.iter().any(|w| f.matches(w)));
+    let rotation = Rotation {
+        workouts,
+        index,
+        filter,
+        cycle,
+        history,
+    };
+    let next = if rotation.get_candidates().is_empty() {
+        index
+    } else {
+        mode.strategy().next_index(&rotation, rng)
+    };
+
+    if workouts
+        .iter()
+        .filter(|w| filter.is_none_or(|f| f.matches(w)))
+        .all(|w| cycle.contains(&w.id))
+    {
+        cycle.clear();
+    }
+
+    next
+}
+
+fn get_last_completion(history: &[HistoryEntry], workout_id: Uuid) -> Option<DateTime<Local>> {
+    history
+        .iter()
+        .filter(|e| e.workout_id == workout_id && e.outcome == Outcome::Completed)
+        .map(|e| e.completed_at)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::{HistoryEntry, Outcome};
+    use crate::ordering::{OrderingMode, get_next_index};
+    use crate::workout::{LabelFilter, Workout};
+    use chrono::{Local, TimeZone};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::HashSet;
+
+    fn create_workouts(count: usize) -> Vec<Workout> {
+        (1..=count)
+            .map(|i| Workout::new(format!("workout{}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_get_next_index_given_sequential_should_wrap() {
+        let workouts = create_workouts(3);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let next = get_next_index(
+            OrderingMode::Sequential,
+            &workouts,
+            2,
+            None,
+            &mut vec![],
+            &[],
+            &mut rng,
+        );
+
+        assert_eq!(0, next)
+    }
+
+    #[test]
+    fn test_get_next_index_given_shuffle_should_visit_each_workout_once_per_cycle() {
+        let workouts = create_workouts(5);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut cycle = vec![];
+        let mut index = 0;
+        let mut visited = HashSet::from([index]);
+
+        for _ in 0..4 {
+            index = get_next_index(
+                OrderingMode::Shuffle,
+                &workouts,
+                index,
+                None,
+                &mut cycle,
+                &[],
+                &mut rng,
+            );
+            visited.insert(index);
+        }
+
+        assert_eq!(5, visited.len())
+    }
+
+    #[test]
+    fn test_get_next_index_given_shuffle_should_not_repeat_current() {
+        let workouts = create_workouts(2);
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut cycle = vec![];
+
+        for _ in 0..10 {
+            let next = get_next_index(
+                OrderingMode::Shuffle,
+                &workouts,
+                0,
+                None,
+                &mut cycle,
+                &[],
+                &mut rng,
+            );
+            assert_eq!(1, next)
+        }
+    }
+
+    #[test]
+    fn test_get_next_index_given_filter_with_single_match_should_pick_it() {
+        let mut workouts = create_workouts(100);
+        workouts[60].category = Some(String::from("legs"));
+        let filter = LabelFilter::Category(String::from("legs"));
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for mode in [OrderingMode::Shuffle, OrderingMode::Weighted] {
+            let mut cycle = vec![];
+            for _ in 0..20 {
+                let next =
+                    get_next_index(mode, &workouts, 0, Some(&filter), &mut cycle, &[], &mut rng);
+                assert_eq!(60, next);
+                assert!(cycle.len() <= 1)
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_next_index_given_filter_without_matches_should_ignore_it() {
+        let workouts = create_workouts(3);
+        let filter = LabelFilter::Tag(String::from("cardio"));
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let next = get_next_index(
+            OrderingMode::Sequential,
+            &workouts,
+            0,
+            Some(&filter),
+            &mut vec![],
+            &[],
+            &mut rng,
+        );
+
+        assert_eq!(1, next)
+    }
+
+    #[test]
+    fn test_get_next_index_given_weighted_should_skip_zero_weights() {
+        let mut workouts = create_workouts(3);
+        workouts[1].pick_weight = 0;
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..20 {
+            let next = get_next_index(
+                OrderingMode::Weighted,
+                &workouts,
+                0,
+                None,
+                &mut vec![],
+                &[],
+                &mut rng,
+            );
+            assert_eq!(2, next)
+        }
+    }
+
+    #[test]
+    fn test_get_next_index_given_weighted_should_favor_heavy_workouts() {
+        let mut workouts = create_workouts(3);
+        workouts[2].pick_weight = 9;
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let heavy_count = (0..100)
+            .filter(|_| {
+                get_next_index(
+                    OrderingMode::Weighted,
+                    &workouts,
+                    0,
+                    None,
+                    &mut vec![],
+                    &[],
+                    &mut rng,
+                ) == 2
+            })
+            .count();
+
+        assert!(heavy_count > 70)
+    }
+
+    #[test]
+    fn test_get_next_index_given_least_recent_should_prefer_never_done() {
+        let workouts = create_workouts(3);
+        let done_at = Local.with_ymd_and_hms(2025, 5, 1, 18, 0, 0).unwrap();
+        let history = vec![HistoryEntry::new(
+            &workouts[1],
+            done_at,
+            Outcome::Completed,
+            None,
+            None,
+        )];
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let next = get_next_index(
+            OrderingMode::LeastRecent,
+            &workouts,
+            0,
+            None,
+            &mut vec![],
+            &history,
+            &mut rng,
+        );
+
+        assert_eq!(2, next)
+    }
+
+    #[test]
+    fn test_get_next_index_given_least_recent_should_pick_oldest_completion() {
+        let workouts = create_workouts(3);
+        let history = vec![
+            HistoryEntry::new(
+                &workouts[1],
+                Local.with_ymd_and_hms(2025, 5, 3, 18, 0, 0).unwrap(),
+                Outcome::Completed,
+                None,
+                None,
+            ),
+            HistoryEntry::new(
+                &workouts[2],
+                Local.with_ymd_and_hms(2025, 5, 2, 18, 0, 0).unwrap(),
+                Outcome::Completed,
+                None,
+                None,
+            ),
+        ];
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let next = get_next_index(
+            OrderingMode::LeastRecent,
+            &workouts,
+            0,
+            None,
+            &mut vec![],
+            &history,
+            &mut rng,
+        );
+
+        assert_eq!(2, next)
+    }
+}
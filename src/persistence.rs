@@ -1,17 +1,158 @@
+use crate::history::HistoryEntry;
+use crate::workout::{Program, Workout};
 use chrono::Local;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
 use std::fs::{File, OpenOptions};
 use std::io::{Error, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+use std::{env, fmt, fs};
+use uuid::Uuid;
 
 const WORKOUTS_JSON: &str = "workouts.json";
 const WINDOW_JSON: &str = "window.json";
+const HISTORY_JSON: &str = "history.json";
 const ERROR_LOG: &str = "error.log";
+/// Left in the data directory once legacy files were looked for, so it happens only once.
+const LEGACY_MARKER: &str = ".legacy-migrated";
+const APP_DIR_NAME: &str = "workout-iterator";
+const DATA_DIR_ENV: &str = "WORKOUT_ITERATOR_DATA_DIR";
+const BACKUP_COUNT: usize = 3;
+/// Minimum age of the newest backup before another one is taken.
+const BACKUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const SCHEMA_VERSION: u64 = 5;
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+const HISTORY_SCHEMA_VERSION: u64 = 2;
 
-#[derive(Serialize, Deserialize, Default)]
+/// Upgrade steps for workouts.json, indexed by the schema version they migrate from.
+const MIGRATIONS: [fn(Value) -> Result<Value, PersistenceError>; SCHEMA_VERSION as usize] = [
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+];
+
+static DATA_PATHS: OnceLock<DataPaths> = OnceLock::new();
+
+#[derive(Debug, PartialEq)]
+struct DataPaths {
+    data_dir: PathBuf,
+    config_dir: PathBuf,
+}
+
+impl DataPaths {
+    fn workouts_json(&self) -> PathBuf {
+        self.data_dir.join(WORKOUTS_JSON)
+    }
+
+    fn window_json(&self) -> PathBuf {
+        self.config_dir.join(WINDOW_JSON)
+    }
+
+    fn history_json(&self) -> PathBuf {
+        self.data_dir.join(HISTORY_JSON)
+    }
+
+    fn error_log(&self) -> PathBuf {
+        self.data_dir.join(ERROR_LOG)
+    }
+
+    fn legacy_marker(&self) -> PathBuf {
+        self.data_dir.join(LEGACY_MARKER)
+    }
+}
+
+#[derive(Debug)]
+pub enum PersistenceError {
+    Io(Error),
+    Parse(serde_json::Error),
+    IndexOutOfRange { index: usize, count: usize },
+    NegativePosition,
+    UnsupportedSchemaVersion { version: u64, supported: u64 },
+    MalformedDocument(&'static str),
+}
+
+impl Display for PersistenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistenceError::Io(error) => write!(f, "{}", error),
+            PersistenceError::Parse(error) => write!(f, "malformed json: {}", error),
+            PersistenceError::IndexOutOfRange { index, count } => write!(
+                f,
+                "invalid workouts.json: index {} out of range for {} workout(s)",
+                index, count
+            ),
+            PersistenceError::NegativePosition => {
+                write!(f, "invalid window.json: negative position(s)")
+            }
+            PersistenceError::UnsupportedSchemaVersion { version, supported } => write!(
+                f,
+                "schema version {} is newer than supported version {}",
+                version, supported
+            ),
+            PersistenceError::MalformedDocument(reason) => {
+                write!(f, "malformed document: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<Error> for PersistenceError {
+    fn from(error: Error) -> Self {
+        PersistenceError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for PersistenceError {
+    fn from(error: serde_json::Error) -> Self {
+        PersistenceError::Parse(error)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WorkoutsState {
-    pub index: i8,
-    pub workouts: Vec<String>,
+    pub schema_version: u64,
+    pub active_program: Uuid,
+    pub programs: Vec<Program>,
+}
+
+impl WorkoutsState {
+    pub fn new(active_program: Uuid, programs: Vec<Program>) -> WorkoutsState {
+        WorkoutsState {
+            schema_version: SCHEMA_VERSION,
+            active_program,
+            programs,
+        }
+    }
+}
+
+impl Default for WorkoutsState {
+    fn default() -> WorkoutsState {
+        let program = Program::default();
+
+        WorkoutsState::new(program.id, vec![program])
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryState {
+    pub schema_version: u64,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl Default for HistoryState {
+    fn default() -> HistoryState {
+        HistoryState {
+            schema_version: HISTORY_SCHEMA_VERSION,
+            entries: vec![],
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -31,38 +172,91 @@ impl Position {
     }
 }
 
-pub fn read_workouts_state() -> WorkoutsState {
-    if let Err(error) = maybe_create_initial_workouts_json() {
-        println!("{}", error);
-        std::process::exit(1);
+/// Resolves the data and config directories, creates them and, on the first run, picks up files
+/// left in the working directory by earlier versions. Must be called before any other read or
+/// write.
+pub fn init_data_paths(data_dir_override: Option<PathBuf>) -> Result<(), Error> {
+    // set before any I/O, so a failure never sends the data to another directory
+    let paths = DATA_PATHS.get_or_init(|| {
+        resolve_data_paths(data_dir_override, |key| env::var_os(key).map(PathBuf::from))
+    });
+    fs::create_dir_all(&paths.data_dir)?;
+    fs::create_dir_all(&paths.config_dir)?;
+    if let Err(error) = migrate_legacy_files(paths, Path::new(".")) {
+        log_error(format!("Failed to migrate legacy files: {}", error)).ok();
     }
 
-    let workouts_state = match read_workouts_json() {
-        Err(error) => {
-            println!("{}", error);
-            std::process::exit(2);
-        }
-        Ok(s) => s,
-    };
+    Ok(())
+}
 
-    if let Err(error) = validate_workouts_state(&workouts_state) {
-        println!("{}", error);
-        std::process::exit(3);
-    }
+pub fn read_workouts_state() -> Result<WorkoutsState, PersistenceError> {
+    maybe_create_initial_workouts_json()?;
+    let path = data_paths().workouts_json();
+    let workouts_state = read_workouts_json_with_fallback(&path)?;
+    validate_workouts_state(&workouts_state)?;
+    persist_migration(&path, &workouts_state)?;
 
-    workouts_state
+    Ok(workouts_state)
 }
 
-pub fn write_workouts_state(workouts_state: WorkoutsState) -> Result<(), Error> {
-    let mut file = File::create(WORKOUTS_JSON)?;
+pub fn write_workouts_state(workouts_state: WorkoutsState) -> Result<(), PersistenceError> {
+    let path = data_paths().workouts_json();
+    if is_backup_due(&path) && read_workouts_json(&path).is_ok() {
+        rotate_backups(&path)?;
+    }
+
     let buffer = serde_json::to_vec(&workouts_state)?;
-    file.write_all(&buffer)?;
+    write_atomically(&path, &buffer)?;
+
+    Ok(())
+}
+
+/// Lists the backups of workouts.json that could replace a damaged primary file.
+pub fn get_workouts_backups() -> Vec<PathBuf> {
+    get_valid_backups(&data_paths().workouts_json())
+}
+
+/// Replaces workouts.json with the given backup, keeping the damaged file for inspection.
+pub fn restore_workouts_backup(backup: &Path) -> Result<WorkoutsState, PersistenceError> {
+    restore_backup(&data_paths().workouts_json(), backup)
+}
+
+/// Clamps the index of workouts.json into range, keeping the damaged file for inspection.
+pub fn reset_workouts_index() -> Result<WorkoutsState, PersistenceError> {
+    reset_index(&data_paths().workouts_json())
+}
+
+/// Replaces workouts.json with an empty state, keeping the damaged file for inspection.
+pub fn reset_workouts_state() -> Result<WorkoutsState, PersistenceError> {
+    start_fresh(&data_paths().workouts_json())
+}
+
+/// Reads history.json, falling back to its backups. A damaged file is kept for inspection.
+pub fn read_history_state() -> Result<HistoryState, PersistenceError> {
+    let path = data_paths().history_json();
+    if !fs::exists(&path)? {
+        return Ok(HistoryState::default());
+    }
+
+    read_with_fallback(&path, read_history_json).inspect_err(|_| {
+        preserve_damaged_file(&path).ok();
+    })
+}
+
+pub fn write_history_state(history_state: &HistoryState) -> Result<(), PersistenceError> {
+    let path = data_paths().history_json();
+    if is_backup_due(&path) && read_history_json(&path).is_ok() {
+        rotate_backups(&path)?;
+    }
+
+    let buffer = serde_json::to_vec(history_state)?;
+    write_atomically(&path, &buffer)?;
 
     Ok(())
 }
 
 pub fn read_window_state() -> Option<Position> {
-    match fs::exists(WINDOW_JSON) {
+    match fs::exists(data_paths().window_json()) {
         Err(error) => {
             println!("{}", error);
 
@@ -93,10 +287,9 @@ pub fn read_window_state() -> Option<Position> {
     }
 }
 
-pub fn write_window_state(position: Position) -> Result<(), Error> {
-    let mut file = File::create(WINDOW_JSON)?;
+pub fn write_window_state(position: Position) -> Result<(), PersistenceError> {
     let buffer = serde_json::to_vec(&WindowState { position })?;
-    file.write_all(&buffer)?;
+    write_atomically(&data_paths().window_json(), &buffer)?;
 
     Ok(())
 }
@@ -108,14 +301,94 @@ pub fn log_error(error: impl AsRef<str>) -> Result<(), Error> {
     let mut file = OpenOptions::new()
         .append(true)
         .create(true)
-        .open(ERROR_LOG)?;
+        .open(data_paths().error_log())?;
     writeln!(file, "{}  -  {}", timestamp, error.as_ref())?;
 
     Ok(())
 }
 
-fn maybe_create_initial_workouts_json() -> Result<(), Error> {
-    if fs::exists(WORKOUTS_JSON)? {
+fn data_paths() -> &'static DataPaths {
+    DATA_PATHS.get_or_init(get_default_data_paths)
+}
+
+#[cfg(not(test))]
+fn get_default_data_paths() -> DataPaths {
+    resolve_data_paths(None, |key| env::var_os(key).map(PathBuf::from))
+}
+
+/// Keeps tests that never call `init_data_paths` away from the user's files.
+#[cfg(test)]
+fn get_default_data_paths() -> DataPaths {
+    let dir = env::temp_dir().join(format!("{}-tests-{}", APP_DIR_NAME, std::process::id()));
+    fs::create_dir_all(&dir).ok();
+
+    resolve_data_paths(Some(dir), |_| None)
+}
+
+fn resolve_data_paths(
+    data_dir_override: Option<PathBuf>,
+    env_var: impl Fn(&str) -> Option<PathBuf>,
+) -> DataPaths {
+    if let Some(dir) = data_dir_override.or_else(|| env_var(DATA_DIR_ENV)) {
+        return DataPaths {
+            data_dir: dir.clone(),
+            config_dir: dir,
+        };
+    }
+
+    let home = env_var("HOME").filter(|p| p.is_absolute());
+    let (data_base, config_base) = if cfg!(target_os = "windows") {
+        let app_data = env_var("APPDATA");
+        (app_data.clone(), app_data)
+    } else if cfg!(target_os = "macos") {
+        let support = home.map(|h| h.join("Library").join("Application Support"));
+        (support.clone(), support)
+    } else {
+        (
+            env_var("XDG_DATA_HOME")
+                .filter(|p| p.is_absolute())
+                .or_else(|| home.clone().map(|h| h.join(".local").join("share"))),
+            env_var("XDG_CONFIG_HOME")
+                .filter(|p| p.is_absolute())
+                .or_else(|| home.map(|h| h.join(".config"))),
+        )
+    };
+
+    let to_app_dir =
+        |base: Option<PathBuf>| base.map_or_else(|| PathBuf::from("."), |b| b.join(APP_DIR_NAME));
+
+    DataPaths {
+        data_dir: to_app_dir(data_base),
+        config_dir: to_app_dir(config_base),
+    }
+}
+
+fn migrate_legacy_files(paths: &DataPaths, legacy_dir: &Path) -> Result<(), Error> {
+    let marker = paths.legacy_marker();
+    if fs::exists(&marker)? {
+        return Ok(());
+    }
+
+    let files = [
+        (legacy_dir.join(WORKOUTS_JSON), paths.workouts_json()),
+        (legacy_dir.join(WINDOW_JSON), paths.window_json()),
+        (legacy_dir.join(ERROR_LOG), paths.error_log()),
+    ];
+
+    for (legacy, target) in files {
+        if fs::exists(&target)? || !fs::exists(&legacy)? {
+            continue;
+        }
+
+        fs::copy(&legacy, &target)?;
+    }
+    fs::write(&marker, "")?;
+
+    Ok(())
+}
+
+fn maybe_create_initial_workouts_json() -> Result<(), PersistenceError> {
+    if fs::exists(data_paths().workouts_json())? {
         return Ok(());
     }
 
@@ -124,84 +397,908 @@ fn maybe_create_initial_workouts_json() -> Result<(), Error> {
     Ok(())
 }
 
-fn read_workouts_json() -> Result<WorkoutsState, Error> {
-    let buffer = fs::read(WORKOUTS_JSON)?;
+fn read_workouts_json(path: &Path) -> Result<WorkoutsState, PersistenceError> {
+    let buffer = fs::read(path)?;
+    let document = migrate_workouts_document(serde_json::from_slice(&buffer)?)?;
 
-    Ok(serde_json::from_slice(&buffer)?)
+    Ok(serde_json::from_value(document)?)
 }
 
-fn read_window_json() -> Result<WindowState, Error> {
-    let buffer = fs::read(WINDOW_JSON)?;
+fn migrate_workouts_document(mut document: Value) -> Result<Value, PersistenceError> {
+    let mut version = get_schema_version(&document)?;
+    if version > SCHEMA_VERSION {
+        return Err(PersistenceError::UnsupportedSchemaVersion {
+            version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+
+    while version < SCHEMA_VERSION {
+        document = MIGRATIONS[version as usize](document)?;
+        version += 1;
+        document[SCHEMA_VERSION_KEY] = Value::from(version);
+    }
+
+    Ok(document)
+}
+
+fn get_schema_version(document: &Value) -> Result<u64, PersistenceError> {
+    let object = document
+        .as_object()
+        .ok_or(PersistenceError::MalformedDocument("expected an object"))?;
+
+    match object.get(SCHEMA_VERSION_KEY) {
+        None => Ok(0),
+        Some(version) => version.as_u64().ok_or(PersistenceError::MalformedDocument(
+            "schema version is not a number",
+        )),
+    }
+}
+
+/// The unversioned format already has the v1 layout, only the version stamp is added.
+fn migrate_v0_to_v1(document: Value) -> Result<Value, PersistenceError> {
+    Ok(document)
+}
+
+/// Turns the plain workout texts into records with a freshly generated, from then on stable id.
+fn migrate_v1_to_v2(mut document: Value) -> Result<Value, PersistenceError> {
+    let workouts = document
+        .get_mut("workouts")
+        .and_then(Value::as_array_mut)
+        .ok_or(PersistenceError::MalformedDocument(
+            "expected a workouts list",
+        ))?;
+
+    for workout in workouts.iter_mut() {
+        if let Some(text) = workout.as_str() {
+            *workout = serde_json::to_value(Workout::new(text.to_owned()))?;
+        }
+    }
+
+    Ok(document)
+}
+
+/// Workouts gained an optional exercise list, v2 records are valid plain-text v3 records.
+fn migrate_v2_to_v3(document: Value) -> Result<Value, PersistenceError> {
+    Ok(document)
+}
+
+/// Moves the single rotation into a default program, which becomes the active one.
+fn migrate_v3_to_v4(mut document: Value) -> Result<Value, PersistenceError> {
+    let object = document
+        .as_object_mut()
+        .ok_or(PersistenceError::MalformedDocument("expected an object"))?;
+    let index = object.remove("index").unwrap_or(Value::from(0));
+    let workouts = object
+        .remove("workouts")
+        .ok_or(PersistenceError::MalformedDocument(
+            "expected a workouts list",
+        ))?;
+    let program = Program::default();
+    let program_id = serde_json::to_value(program.id)?;
+
+    object.insert("active_program".to_owned(), program_id.clone());
+    object.insert(
+        "programs".to_owned(),
+        serde_json::json!([{
+            "id": program_id,
+            "name": program.name,
+            "index": index,
+            "workouts": workouts,
+        }]),
+    );
+
+    Ok(document)
+}
+
+/// Rotation indices became unsigned, so negative or otherwise unreadable ones start over.
+fn migrate_v4_to_v5(mut document: Value) -> Result<Value, PersistenceError> {
+    let programs = document
+        .get_mut("programs")
+        .and_then(Value::as_array_mut)
+        .ok_or(PersistenceError::MalformedDocument(
+            "expected a programs list",
+        ))?;
+    for program in programs {
+        let program = program
+            .as_object_mut()
+            .ok_or(PersistenceError::MalformedDocument(
+                "expected a program object",
+            ))?;
+        if !program.get("index").is_some_and(Value::is_u64) {
+            program.insert("index".to_owned(), Value::from(0));
+        }
+    }
+
+    Ok(document)
+}
+
+/// Writes a document read from an older schema back once, so the ids generated while
+/// migrating stay stable. The old file is kept as the first backup.
+fn persist_migration(path: &Path, workouts_state: &WorkoutsState) -> Result<(), PersistenceError> {
+    let stored_version = fs::read(path)
+        .ok()
+        .and_then(|buffer| serde_json::from_slice::<Value>(&buffer).ok())
+        .and_then(|document| get_schema_version(&document).ok());
+    if stored_version.is_none_or(|v| v >= SCHEMA_VERSION) || read_workouts_json(path).is_err() {
+        return Ok(());
+    }
+
+    rotate_backups(path)?;
+    write_atomically(path, &serde_json::to_vec(workouts_state)?)?;
+
+    Ok(())
+}
+
+fn read_workouts_json_with_fallback(path: &Path) -> Result<WorkoutsState, PersistenceError> {
+    read_with_fallback(path, read_workouts_json)
+}
+
+fn read_history_json(path: &Path) -> Result<HistoryState, PersistenceError> {
+    let buffer = fs::read(path)?;
+    let history_state: HistoryState = serde_json::from_slice(&buffer)?;
+    if history_state.schema_version > HISTORY_SCHEMA_VERSION {
+        return Err(PersistenceError::UnsupportedSchemaVersion {
+            version: history_state.schema_version,
+            supported: HISTORY_SCHEMA_VERSION,
+        });
+    }
+
+    Ok(history_state)
+}
+
+fn read_with_fallback<T>(
+    path: &Path,
+    read: fn(&Path) -> Result<T, PersistenceError>,
+) -> Result<T, PersistenceError> {
+    let error = match read(path) {
+        Ok(state) => return Ok(state),
+        Err(error) => error,
+    };
+
+    for backup in (1..=BACKUP_COUNT).map(|n| get_backup_path(path, n)) {
+        if let Ok(state) = read(&backup) {
+            log_error(format!(
+                "{}: {}, falling back to {}",
+                path.display(),
+                error,
+                backup.display()
+            ))
+            .ok();
+
+            return Ok(state);
+        }
+    }
+
+    Err(error)
+}
+
+fn get_valid_backups(path: &Path) -> Vec<PathBuf> {
+    (1..=BACKUP_COUNT)
+        .map(|n| get_backup_path(path, n))
+        .filter(|backup| {
+            read_workouts_json(backup).is_ok_and(|state| validate_workouts_state(&state).is_ok())
+        })
+        .collect()
+}
+
+fn restore_backup(path: &Path, backup: &Path) -> Result<WorkoutsState, PersistenceError> {
+    let workouts_state = read_workouts_json(backup)?;
+    validate_workouts_state(&workouts_state)?;
+    preserve_damaged_file(path)?;
+    write_atomically(path, &serde_json::to_vec(&workouts_state)?)?;
+
+    Ok(workouts_state)
+}
+
+fn reset_index(path: &Path) -> Result<WorkoutsState, PersistenceError> {
+    let mut workouts_state = read_workouts_json(path)?;
+    for program in workouts_state.programs.iter_mut() {
+        program.index = clamp_index(program.index, program.workouts.len());
+    }
+    preserve_damaged_file(path)?;
+    write_atomically(path, &serde_json::to_vec(&workouts_state)?)?;
+
+    Ok(workouts_state)
+}
+
+fn start_fresh(path: &Path) -> Result<WorkoutsState, PersistenceError> {
+    let workouts_state = WorkoutsState::default();
+    preserve_damaged_file(path)?;
+    write_atomically(path, &serde_json::to_vec(&workouts_state)?)?;
+
+    Ok(workouts_state)
+}
+
+fn clamp_index(index: usize, count: usize) -> usize {
+    index.min(count.saturating_sub(1))
+}
+
+fn preserve_damaged_file(path: &Path) -> Result<(), Error> {
+    if !fs::exists(path)? {
+        return Ok(());
+    }
+
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    fs::copy(
+        path,
+        append_to_file_name(path, &format!(".damaged.{}", timestamp)),
+    )?;
+
+    Ok(())
+}
+
+/// The temporary file has a unique name, so the window and a command can write at the same time.
+pub(crate) fn write_atomically(path: &Path, buffer: &[u8]) -> Result<(), Error> {
+    let temp_path = append_to_file_name(path, &format!(".{}.tmp", Uuid::new_v4().simple()));
+    let mut file = File::create(&temp_path)?;
+    file.write_all(buffer)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+fn rotate_backups(path: &Path) -> Result<(), Error> {
+    for n in (1..BACKUP_COUNT).rev() {
+        let backup = get_backup_path(path, n);
+        if fs::exists(&backup)? {
+            fs::rename(&backup, get_backup_path(path, n + 1))?;
+        }
+    }
+
+    let newest = get_backup_path(path, 1);
+    fs::copy(path, &newest)?;
+    // some platforms keep the time of the original on copies
+    File::options()
+        .write(true)
+        .open(&newest)?
+        .set_modified(SystemTime::now())?;
+
+    Ok(())
+}
+
+/// Keeps the backups apart in time, since every move of the rotation writes the file.
+fn is_backup_due(path: &Path) -> bool {
+    fs::metadata(get_backup_path(path, 1))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_none_or(|age| age >= BACKUP_INTERVAL)
+}
+
+fn get_backup_path(path: &Path, n: usize) -> PathBuf {
+    append_to_file_name(path, &format!(".bak.{}", n))
+}
+
+fn append_to_file_name(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+
+    path.with_file_name(file_name)
+}
+
+fn read_window_json() -> Result<WindowState, PersistenceError> {
+    let buffer = fs::read(data_paths().window_json())?;
 
     Ok(serde_json::from_slice(&buffer)?)
 }
 
-fn validate_workouts_state(workouts_state: &WorkoutsState) -> Result<(), &'static str> {
-    let count = workouts_state.workouts.len() as i8;
-    match workouts_state.index {
-        i if i < 0 || (count == 0 && i != 0) || (count > 0 && i >= count) => {
-            Err("invalid workouts.json: index out of range")
+fn validate_workouts_state(workouts_state: &WorkoutsState) -> Result<(), PersistenceError> {
+    if workouts_state
+        .programs
+        .iter()
+        .all(|p| p.id != workouts_state.active_program)
+    {
+        return Err(PersistenceError::MalformedDocument(
+            "active program not found",
+        ));
+    }
+
+    workouts_state
+        .programs
+        .iter()
+        .try_for_each(validate_program)
+}
+
+fn validate_program(program: &Program) -> Result<(), PersistenceError> {
+    let count = program.workouts.len();
+    match program.index {
+        i if (count == 0 && i != 0) || (count > 0 && i >= count) => {
+            Err(PersistenceError::IndexOutOfRange { index: i, count })
         }
         _ => Ok(()),
     }
 }
 
-fn validate_window_state(window_state: &WindowState) -> Result<(), &'static str> {
+fn validate_window_state(window_state: &WindowState) -> Result<(), PersistenceError> {
     match window_state.position {
-        Position { x, y } if x < 0.0 || y < 0.0 => Err("invalid window.json: negative position(s)"),
+        Position { x, y } if x < 0.0 || y < 0.0 => Err(PersistenceError::NegativePosition),
         _ => Ok(()),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::ordering::OrderingMode;
     use crate::persistence::{
-        Position, WindowState, WorkoutsState, validate_window_state, validate_workouts_state,
+        BACKUP_COUNT, DataPaths, PersistenceError, Position, SCHEMA_VERSION, WindowState,
+        WorkoutsState, clamp_index, get_backup_path, get_valid_backups, is_backup_due,
+        migrate_legacy_files, migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3,
+        migrate_v3_to_v4, migrate_v4_to_v5, migrate_workouts_document, persist_migration,
+        read_history_json, read_workouts_json, read_workouts_json_with_fallback, reset_index,
+        resolve_data_paths, restore_backup, rotate_backups, start_fresh, validate_window_state,
+        validate_workouts_state, write_atomically,
     };
+    use crate::workout::{Program, Workout};
+    use serde_json::{Value, json};
+    use std::fs;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    const WORKOUTS_V0_JSON: &str = include_str!("../tests/fixtures/workouts_v0.json");
+    const WORKOUTS_V1_JSON: &str = include_str!("../tests/fixtures/workouts_v1.json");
+    const WORKOUTS_V2_JSON: &str = include_str!("../tests/fixtures/workouts_v2.json");
+    const WORKOUTS_V3_JSON: &str = include_str!("../tests/fixtures/workouts_v3.json");
+    const WORKOUTS_V4_JSON: &str = include_str!("../tests/fixtures/workouts_v4.json");
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "workout-iterator-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
 
     #[test]
-    fn test_validate_workouts_state_given_default_should_be_ok() {
+    fn test_resolve_data_paths_given_override_should_use_override_for_both() {
+        let paths = resolve_data_paths(Some(PathBuf::from("/custom")), |_| {
+            Some(PathBuf::from("/env"))
+        });
+
+        assert_eq!(
+            DataPaths {
+                data_dir: PathBuf::from("/custom"),
+                config_dir: PathBuf::from("/custom"),
+            },
+            paths
+        )
+    }
+
+    #[test]
+    fn test_resolve_data_paths_given_env_var_should_use_env_var_for_both() {
+        let paths = resolve_data_paths(None, |key| {
+            (key == "WORKOUT_ITERATOR_DATA_DIR").then(|| PathBuf::from("/env"))
+        });
+
+        assert_eq!(PathBuf::from("/env"), paths.data_dir);
+        assert_eq!(PathBuf::from("/env"), paths.config_dir)
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_resolve_data_paths_given_xdg_vars_should_use_xdg_dirs() {
+        let paths = resolve_data_paths(None, |key| match key {
+            "XDG_DATA_HOME" => Some(PathBuf::from("/xdg/data")),
+            "XDG_CONFIG_HOME" => Some(PathBuf::from("/xdg/config")),
+            "HOME" => Some(PathBuf::from("/home/user")),
+            _ => None,
+        });
+
+        assert_eq!(PathBuf::from("/xdg/data/workout-iterator"), paths.data_dir);
+        assert_eq!(
+            PathBuf::from("/xdg/config/workout-iterator"),
+            paths.config_dir
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_resolve_data_paths_given_only_home_should_use_xdg_defaults() {
+        let paths = resolve_data_paths(None, |key| {
+            (key == "HOME").then(|| PathBuf::from("/home/user"))
+        });
+
+        assert_eq!(
+            PathBuf::from("/home/user/.local/share/workout-iterator"),
+            paths.data_dir
+        );
+        assert_eq!(
+            PathBuf::from("/home/user/.config/workout-iterator"),
+            paths.config_dir
+        )
+    }
+
+    #[test]
+    fn test_resolve_data_paths_given_no_env_should_fall_back_to_working_directory() {
+        let paths = resolve_data_paths(None, |_| None);
+
+        assert_eq!(PathBuf::from("."), paths.data_dir);
+        assert_eq!(PathBuf::from("."), paths.config_dir)
+    }
+
+    #[test]
+    fn test_migrate_legacy_files_given_legacy_files_should_copy_them() {
+        let legacy_dir = create_temp_dir("legacy");
+        let target_dir = create_temp_dir("target");
+        fs::write(legacy_dir.join("workouts.json"), "legacy").unwrap();
+        let paths = DataPaths {
+            data_dir: target_dir.clone(),
+            config_dir: target_dir.clone(),
+        };
+
+        migrate_legacy_files(&paths, &legacy_dir).unwrap();
+
+        assert_eq!(
+            "legacy",
+            fs::read_to_string(target_dir.join("workouts.json")).unwrap()
+        );
+        assert!(!target_dir.join("window.json").exists())
+    }
+
+    #[test]
+    fn test_migrate_legacy_files_given_earlier_migration_should_skip() {
+        let legacy_dir = create_temp_dir("legacy");
+        let target_dir = create_temp_dir("target");
+        let paths = DataPaths {
+            data_dir: target_dir.clone(),
+            config_dir: target_dir.clone(),
+        };
+        migrate_legacy_files(&paths, &legacy_dir).unwrap();
+        fs::write(legacy_dir.join("workouts.json"), "legacy").unwrap();
+
+        migrate_legacy_files(&paths, &legacy_dir).unwrap();
+
+        assert!(!target_dir.join("workouts.json").exists())
+    }
+
+    #[test]
+    fn test_migrate_legacy_files_given_existing_target_should_keep_target() {
+        let legacy_dir = create_temp_dir("legacy");
+        let target_dir = create_temp_dir("target");
+        fs::write(legacy_dir.join("workouts.json"), "legacy").unwrap();
+        fs::write(target_dir.join("workouts.json"), "current").unwrap();
+        let paths = DataPaths {
+            data_dir: target_dir.clone(),
+            config_dir: target_dir.clone(),
+        };
+
+        migrate_legacy_files(&paths, &legacy_dir).unwrap();
+
+        assert_eq!(
+            "current",
+            fs::read_to_string(target_dir.join("workouts.json")).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_write_atomically_should_replace_content_and_leave_no_temp_file() {
+        let dir = create_temp_dir("atomic");
+        let path = dir.join("workouts.json");
+        fs::write(&path, "old").unwrap();
+
+        write_atomically(&path, b"new").unwrap();
+
+        assert_eq!("new", fs::read_to_string(&path).unwrap());
+        assert_eq!(1, fs::read_dir(&dir).unwrap().count())
+    }
+
+    #[test]
+    fn test_is_backup_due_given_fresh_backup_should_be_false() {
+        let dir = create_temp_dir("due");
+        let path = dir.join("workouts.json");
+        fs::write(&path, "content").unwrap();
+        assert!(is_backup_due(&path));
+
+        rotate_backups(&path).unwrap();
+
+        assert!(!is_backup_due(&path))
+    }
+
+    #[test]
+    fn test_rotate_backups_should_keep_latest_copies() {
+        let dir = create_temp_dir("rotate");
+        let path = dir.join("workouts.json");
+
+        for n in 0..=BACKUP_COUNT {
+            fs::write(&path, n.to_string()).unwrap();
+            rotate_backups(&path).unwrap();
+        }
+
+        assert_eq!(
+            BACKUP_COUNT.to_string(),
+            fs::read_to_string(get_backup_path(&path, 1)).unwrap()
+        );
+        assert_eq!(
+            "1",
+            fs::read_to_string(get_backup_path(&path, BACKUP_COUNT)).unwrap()
+        );
+        assert!(!get_backup_path(&path, BACKUP_COUNT + 1).exists())
+    }
+
+    #[test]
+    fn test_read_workouts_json_with_fallback_given_truncated_primary_should_read_backup() {
+        let dir = create_temp_dir("fallback");
+        let path = dir.join("workouts.json");
+        fs::write(&path, r#"{"index":0,"worko"#).unwrap();
+        fs::write(get_backup_path(&path, 1), "garbage").unwrap();
+        fs::write(
+            get_backup_path(&path, 2),
+            r#"{"index":1,"workouts":["a","b"]}"#,
+        )
+        .unwrap();
+
+        let state = read_workouts_json_with_fallback(&path).unwrap();
+
+        assert_eq!(1, state.programs[0].index);
+        assert_eq!(vec!["a", "b"], get_texts(&state))
+    }
+
+    #[test]
+    fn test_persist_migration_given_legacy_document_should_keep_generated_ids() {
+        let dir = create_temp_dir("persist");
+        let path = dir.join("workouts.json");
+        fs::write(&path, WORKOUTS_V1_JSON).unwrap();
+        let state = read_workouts_json(&path).unwrap();
+
+        persist_migration(&path, &state).unwrap();
+        let reread = read_workouts_json(&path).unwrap();
+
+        assert_eq!(state.active_program, reread.active_program);
+        assert_eq!(get_ids(&state), get_ids(&reread));
+        assert_eq!(
+            WORKOUTS_V1_JSON,
+            fs::read_to_string(get_backup_path(&path, 1)).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_persist_migration_given_current_document_should_not_write() {
+        let dir = create_temp_dir("persist");
+        let path = dir.join("workouts.json");
         let state = WorkoutsState::default();
+        write_atomically(&path, &serde_json::to_vec(&state).unwrap()).unwrap();
+
+        persist_migration(&path, &state).unwrap();
+
+        assert!(!get_backup_path(&path, 1).exists())
+    }
+
+    #[test]
+    fn test_read_workouts_json_with_fallback_given_no_valid_file_should_be_err() {
+        let dir = create_temp_dir("fallback");
+        let path = dir.join("workouts.json");
+        fs::write(&path, "garbage").unwrap();
+
+        assert!(read_workouts_json_with_fallback(&path).is_err())
+    }
+
+    fn parse_fixture(fixture: &str) -> Value {
+        serde_json::from_str(fixture).unwrap()
+    }
+
+    fn get_texts(state: &WorkoutsState) -> Vec<&str> {
+        state.programs[0]
+            .workouts
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect()
+    }
+
+    fn get_ids(state: &WorkoutsState) -> Vec<Uuid> {
+        state.programs[0].workouts.iter().map(|w| w.id).collect()
+    }
+
+    fn create_state(index: usize, workouts: Vec<Workout>) -> WorkoutsState {
+        let program = Program {
+            index,
+            workouts,
+            ..Program::default()
+        };
+
+        WorkoutsState::new(program.id, vec![program])
+    }
+
+    #[test]
+    fn test_migrate_v0_to_v1_should_keep_layout() {
+        let document = migrate_v0_to_v1(parse_fixture(WORKOUTS_V0_JSON)).unwrap();
+
+        assert_eq!(
+            json!({"index": 1, "workouts": ["Push", "Pull", "Legs"]}),
+            document
+        )
+    }
+
+    #[test]
+    fn test_migrate_v1_to_v2_should_create_records_with_unique_ids() {
+        let document = migrate_v1_to_v2(parse_fixture(WORKOUTS_V1_JSON)).unwrap();
+        let workouts: Vec<Workout> = serde_json::from_value(document["workouts"].clone()).unwrap();
+
+        assert_eq!(
+            vec!["Push", "Pull", "Legs"],
+            workouts.iter().map(|w| w.text.as_str()).collect::<Vec<_>>()
+        );
+        assert_ne!(workouts[0].id, workouts[1].id);
+        assert_ne!(workouts[1].id, workouts[2].id)
+    }
+
+    #[test]
+    fn test_migrate_v1_to_v2_given_missing_workouts_should_be_err() {
+        assert!(matches!(
+            migrate_v1_to_v2(json!({"schema_version": 1, "index": 0})),
+            Err(PersistenceError::MalformedDocument(_))
+        ))
+    }
 
+    #[test]
+    fn test_migrate_v2_to_v3_should_keep_ids_and_texts() {
+        let document = migrate_v2_to_v3(parse_fixture(WORKOUTS_V2_JSON)).unwrap();
+
+        assert_eq!(parse_fixture(WORKOUTS_V2_JSON), document)
+    }
+
+    #[test]
+    fn test_migrate_workouts_document_given_v3_should_keep_exercises() {
+        let document = migrate_workouts_document(parse_fixture(WORKOUTS_V3_JSON)).unwrap();
+        let state: WorkoutsState = serde_json::from_value(document).unwrap();
+
+        assert_eq!(2, state.programs[0].workouts[0].exercises.len());
+        assert!(state.programs[0].workouts[2].exercises.is_empty())
+    }
+
+    #[test]
+    fn test_migrate_v3_to_v4_should_wrap_rotation_into_active_program() {
+        let document = migrate_v3_to_v4(parse_fixture(WORKOUTS_V3_JSON)).unwrap();
+
+        assert_eq!(document["active_program"], document["programs"][0]["id"]);
+        assert_eq!(0, document["programs"][0]["index"]);
+        assert_eq!(
+            3,
+            document["programs"][0]["workouts"]
+                .as_array()
+                .unwrap()
+                .len()
+        );
+        assert!(document.get("workouts").is_none())
+    }
+
+    #[test]
+    fn test_migrate_v4_to_v5_given_negative_index_should_reset_to_zero() {
+        let document = migrate_v4_to_v5(json!({
+            "schema_version": 4,
+            "programs": [{"index": -1, "workouts": []}, {"index": 2, "workouts": []}],
+        }))
+        .unwrap();
+
+        assert_eq!(0, document["programs"][0]["index"]);
+        assert_eq!(2, document["programs"][1]["index"])
+    }
+
+    #[test]
+    fn test_migrate_v4_to_v5_given_missing_programs_should_be_err() {
+        assert!(matches!(
+            migrate_v4_to_v5(json!({"schema_version": 4})),
+            Err(PersistenceError::MalformedDocument(_))
+        ))
+    }
+
+    #[test]
+    fn test_migrate_workouts_document_given_v4_should_keep_programs() {
+        let document = migrate_workouts_document(parse_fixture(WORKOUTS_V4_JSON)).unwrap();
+        let state: WorkoutsState = serde_json::from_value(document).unwrap();
+
+        assert_eq!(2, state.programs.len());
+        assert_eq!(state.programs[1].id, state.active_program);
         assert!(validate_workouts_state(&state).is_ok())
     }
 
     #[test]
-    fn test_validate_workouts_state_given_index_in_range_should_be_ok() {
+    fn test_read_v4_document_without_ordering_should_default_to_sequential() {
+        let document = migrate_workouts_document(parse_fixture(WORKOUTS_V4_JSON)).unwrap();
+        let state: WorkoutsState = serde_json::from_value(document).unwrap();
+
+        assert_eq!(OrderingMode::Sequential, state.programs[0].ordering);
+        assert!(
+            state.programs[0]
+                .workouts
+                .iter()
+                .all(|w| w.pick_weight == 1)
+        )
+    }
+
+    #[test]
+    fn test_migrate_workouts_document_should_reach_current_version() {
+        for fixture in [
+            WORKOUTS_V0_JSON,
+            WORKOUTS_V1_JSON,
+            WORKOUTS_V2_JSON,
+            WORKOUTS_V3_JSON,
+            WORKOUTS_V4_JSON,
+        ] {
+            let document = migrate_workouts_document(parse_fixture(fixture)).unwrap();
+            let state: WorkoutsState = serde_json::from_value(document).unwrap();
+
+            assert_eq!(SCHEMA_VERSION, state.schema_version);
+            assert_eq!(vec!["Push", "Pull", "Legs"], get_texts(&state));
+        }
+    }
+
+    #[test]
+    fn test_migrate_workouts_document_given_newer_version_should_be_err() {
+        let document = json!({"schema_version": SCHEMA_VERSION + 1, "index": 0, "workouts": []});
+
+        assert!(matches!(
+            migrate_workouts_document(document),
+            Err(PersistenceError::UnsupportedSchemaVersion { .. })
+        ))
+    }
+
+    #[test]
+    fn test_migrate_workouts_document_given_non_object_should_be_err() {
+        assert!(matches!(
+            migrate_workouts_document(json!(["Push", "Pull"])),
+            Err(PersistenceError::MalformedDocument(_))
+        ))
+    }
+
+    #[test]
+    fn test_read_history_json_given_newer_version_should_be_err() {
+        let dir = create_temp_dir("history");
+        let path = dir.join("history.json");
+        fs::write(&path, r#"{"schema_version":99,"entries":[]}"#).unwrap();
+
+        assert!(matches!(
+            read_history_json(&path),
+            Err(PersistenceError::UnsupportedSchemaVersion { version: 99, .. })
+        ))
+    }
+
+    #[test]
+    fn test_read_history_json_should_read_entries() {
+        let dir = create_temp_dir("history");
+        let path = dir.join("history.json");
+        fs::write(
+            &path,
+            r#"{"schema_version":1,"entries":[{"workout_id":"6f1c2b8e-3f0a-4c7e-9d51-2a8b7e4c1d01","workout_text":"Push","completed_at":"2026-10-18T07:30:00+02:00","duration_secs":2700}]}"#,
+        )
+        .unwrap();
+
+        let history_state = read_history_json(&path).unwrap();
+
+        assert_eq!(1, history_state.entries.len());
+        assert_eq!(Some(2700), history_state.entries[0].duration_secs)
+    }
+
+    #[test]
+    fn test_clamp_index_given_index_beyond_end_should_return_last() {
+        assert_eq!(2, clamp_index(7, 3))
+    }
+
+    #[test]
+    fn test_clamp_index_given_index_beyond_former_limit_should_keep_index() {
+        assert_eq!(200, clamp_index(200, 300))
+    }
+
+    #[test]
+    fn test_clamp_index_given_empty_list_should_return_zero() {
+        assert_eq!(0, clamp_index(5, 0))
+    }
+
+    #[test]
+    fn test_reset_index_should_clamp_and_preserve_damaged_file() {
+        let dir = create_temp_dir("reset");
+        let path = dir.join("workouts.json");
+        fs::write(&path, r#"{"index":5,"workouts":["a","b"]}"#).unwrap();
+
+        let state = reset_index(&path).unwrap();
+
+        assert_eq!(1, state.programs[0].index);
+        assert!(validate_workouts_state(&state).is_ok());
+        assert_eq!(2, fs::read_dir(&dir).unwrap().count())
+    }
+
+    #[test]
+    fn test_restore_backup_should_replace_primary() {
+        let dir = create_temp_dir("restore");
+        let path = dir.join("workouts.json");
+        fs::write(&path, r#"{"index":9,"workouts":[]}"#).unwrap();
+        fs::write(get_backup_path(&path, 1), r#"{"index":0,"workouts":["a"]}"#).unwrap();
+
+        assert_eq!(vec![get_backup_path(&path, 1)], get_valid_backups(&path));
+
+        let state = restore_backup(&path, &get_backup_path(&path, 1)).unwrap();
+
+        assert_eq!(vec!["a"], get_texts(&state));
+        assert!(
+            read_workouts_json_with_fallback(&path)
+                .is_ok_and(|s| validate_workouts_state(&s).is_ok())
+        )
+    }
+
+    #[test]
+    fn test_start_fresh_should_write_default_and_preserve_damaged_file() {
+        let dir = create_temp_dir("fresh");
+        let path = dir.join("workouts.json");
+        fs::write(&path, "garbage").unwrap();
+
+        let state = start_fresh(&path).unwrap();
+
+        assert!(state.programs[0].workouts.is_empty());
+        assert_eq!(2, fs::read_dir(&dir).unwrap().count())
+    }
+
+    #[test]
+    fn test_validate_workouts_state_given_index_not_in_range_should_report_index() {
+        let state = create_state(3, vec![Workout::new(String::from("workout1"))]);
+
+        assert!(matches!(
+            validate_workouts_state(&state),
+            Err(PersistenceError::IndexOutOfRange { index: 3, count: 1 })
+        ))
+    }
+
+    #[test]
+    fn test_validate_workouts_state_given_unknown_active_program_should_be_err() {
         let state = WorkoutsState {
-            workouts: vec![String::from("workout1"), String::from("workout2")],
-            index: 1,
+            active_program: Uuid::new_v4(),
+            ..WorkoutsState::default()
         };
 
+        assert!(matches!(
+            validate_workouts_state(&state),
+            Err(PersistenceError::MalformedDocument(_))
+        ))
+    }
+
+    #[test]
+    fn test_validate_workouts_state_given_default_should_be_ok() {
+        let state = WorkoutsState::default();
+
+        assert!(validate_workouts_state(&state).is_ok())
+    }
+
+    #[test]
+    fn test_validate_workouts_state_given_index_in_range_should_be_ok() {
+        let state = create_state(
+            1,
+            vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+        );
+
         assert!(validate_workouts_state(&state).is_ok())
     }
 
     #[test]
     fn test_validate_workouts_state_given_index_not_in_range_should_be_err() {
-        let state = WorkoutsState {
-            workouts: vec![String::from("workout1"), String::from("workout2")],
-            index: 2,
-        };
+        let state = create_state(
+            2,
+            vec![
+                Workout::new(String::from("workout1")),
+                Workout::new(String::from("workout2")),
+            ],
+        );
 
         assert!(validate_workouts_state(&state).is_err())
     }
 
     #[test]
-    fn test_validate_workouts_state_given_negative_index_should_be_err() {
-        let state = WorkoutsState {
-            workouts: vec![String::from("workout1"), String::from("workout2")],
-            index: -1,
-        };
+    fn test_validate_workouts_state_given_index_beyond_former_limit_should_be_ok() {
+        let workouts = (0..300)
+            .map(|i| Workout::new(format!("workout{}", i)))
+            .collect();
+        let state = create_state(250, workouts);
 
-        assert!(validate_workouts_state(&state).is_err())
+        assert!(validate_workouts_state(&state).is_ok())
     }
 
     #[test]
     fn test_validate_workouts_state_given_empty_list_index_not_zero_should_be_err() {
-        let state = WorkoutsState {
-            workouts: vec![],
-            index: 1,
-        };
+        let state = create_state(1, vec![]);
 
         assert!(validate_workouts_state(&state).is_err())
     }
@@ -0,0 +1,427 @@
+use crate::history::{HistoryEntry, Outcome, SetLog};
+use crate::workout::{Exercise, Load, LoadUnit};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+/// Loads computed from a percentage are rounded to what can be put on a bar.
+const KG_STEP: f32 = 2.5;
+const LB_STEP: f32 = 5.0;
+
+/// How the targets of an exercise grow from one session to the next.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Progression {
+    /// Adds `increment` to the load once every set reached the target reps.
+    Linear { increment: f32 },
+    /// Adds a rep per session up to `max_reps`, then adds `increment` and starts over at the
+    /// target reps.
+    Double { max_reps: u32, increment: f32 },
+    /// Works at `percent` of a training max that grows by `increment` with every successful
+    /// session.
+    TrainingMax {
+        percent: f32,
+        max: Load,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        increment: Option<f32>,
+    },
+}
+
+/// Collects the progression tokens of the editor notation, which may come in any order.
+#[derive(Debug, Default)]
+pub struct ProgressionTargets {
+    /// The amount and the unit it was given in, if any.
+    pub increment: Option<(f32, Option<LoadUnit>)>,
+    pub max_reps: Option<u32>,
+    pub training_max: Option<(f32, Load)>,
+    /// Increments in this unit are not read, since they would be added to a load in another unit.
+    pub rejected_unit: Option<LoadUnit>,
+}
+
+impl ProgressionTargets {
+    /// Reads `+<increment>[kg|lb]` and `<percent>%tm<amount><kg|lb>` tokens.
+    pub fn apply(&mut self, token: &str) -> bool {
+        if self.increment.is_none()
+            && let Some(increment) = token
+                .strip_prefix('+')
+                .and_then(parse_increment)
+                .filter(|(_, unit)| unit.is_none() || *unit != self.rejected_unit)
+        {
+            self.increment = Some(increment);
+            return true;
+        }
+        if self.training_max.is_none()
+            && let Some((percent, max)) = token.split_once("%tm")
+            && let Some(percent) = percent.parse::<f32>().ok().filter(|p| *p > 0.0)
+            && let Some(max) = Load::parse(max)
+        {
+            self.training_max = Some((percent, max));
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns the unit of the increment if it differs from the unit of the load it is added to,
+    /// which is the training max if there is one.
+    pub fn get_conflicting_unit(&self, load: Option<&Load>) -> Option<LoadUnit> {
+        let target = self
+            .training_max
+            .as_ref()
+            .map(|(_, max)| max.unit)
+            .or(load.map(|l| l.unit))?;
+
+        self.increment
+            .and_then(|(_, unit)| unit)
+            .filter(|unit| *unit != target)
+    }
+
+    pub fn build(self) -> Option<Progression> {
+        let increment = self.increment.map(|(amount, _)| amount);
+        match (self.training_max, self.max_reps, increment) {
+            (Some((percent, max)), _, increment) => Some(Progression::TrainingMax {
+                percent,
+                max,
+                increment,
+            }),
+            (None, Some(max_reps), increment) => Some(Progression::Double {
+                max_reps,
+                increment: increment.unwrap_or(0.0),
+            }),
+            (None, None, Some(increment)) => Some(Progression::Linear { increment }),
+            (None, None, None) => None,
+        }
+    }
+}
+
+/// Targets for the next session of an exercise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub reps: Option<u32>,
+    pub load: Option<Load>,
+}
+
+impl Suggestion {
+    /// Returns the exercise with its targets replaced by the suggested ones.
+    pub fn apply(&self, exercise: &Exercise) -> Exercise {
+        Exercise {
+            reps: self.reps.or(exercise.reps),
+            load: self.load.clone().or(exercise.load.clone()),
+            ..exercise.clone()
+        }
+    }
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(reps) = self.reps {
+            parts.push(format!("{} reps", reps));
+        }
+        if let Some(load) = &self.load {
+            parts.push(format!("{} {}", load.amount, load.unit));
+        }
+
+        write!(f, "{}", parts.join(" \u{b7} "))
+    }
+}
+
+/// Computes the next targets of an exercise from the sets logged for it in earlier sessions
+/// of the workout. Returns `None` when the exercise has no progression or nothing to base
+/// it on yet.
+pub fn suggest(
+    exercise: &Exercise,
+    workout_id: Uuid,
+    history: &[HistoryEntry],
+) -> Option<Suggestion> {
+    let progression = exercise.progression.as_ref()?;
+    let sessions = get_sessions(exercise, workout_id, history);
+    let last = sessions.last();
+
+    match progression {
+        Progression::Linear { increment } => {
+            let last = last?;
+            let load = get_top_load(last).or(exercise.load.clone())?;
+            let amount = if is_successful(exercise, last, exercise.reps) {
+                load.amount + increment
+            } else {
+                load.amount
+            };
+
+            Some(Suggestion {
+                reps: exercise.reps,
+                load: Some(Load { amount, ..load }),
+            })
+        }
+        Progression::Double {
+            max_reps,
+            increment,
+        } => {
+            let last = last?;
+            let min_reps = exercise.reps.unwrap_or(1);
+            let load = get_top_load(last).or(exercise.load.clone());
+            if is_successful(exercise, last, Some(*max_reps)) {
+                return Some(Suggestion {
+                    reps: Some(min_reps),
+                    load: load.map(|l| Load {
+                        amount: l.amount + increment,
+                        ..l
+                    }),
+                });
+            }
+
+            let lowest = last.iter().filter_map(|s| s.reps).min().unwrap_or(0);
+            Some(Suggestion {
+                reps: Some((lowest + 1).clamp(min_reps, *max_reps)),
+                load,
+            })
+        }
+        Progression::TrainingMax {
+            percent,
+            max,
+            increment,
+        } => {
+            let successes = sessions
+                .iter()
+                .filter(|s| is_successful(exercise, s, exercise.reps))
+                .count() as f32;
+            let training_max = max.amount + increment.unwrap_or(0.0) * successes;
+
+            Some(Suggestion {
+                reps: exercise.reps,
+                load: Some(Load {
+                    amount: round_load(training_max * percent / 100.0, max.unit),
+                    unit: max.unit,
+                }),
+            })
+        }
+    }
+}
+
+/// Returns the sets logged for the exercise per session of the workout, oldest first.
+fn get_sessions<'a>(
+    exercise: &Exercise,
+    workout_id: Uuid,
+    history: &'a [HistoryEntry],
+) -> Vec<Vec<&'a SetLog>> {
+    let mut entries: Vec<_> = history
+        .iter()
+        .filter(|e| e.workout_id == workout_id && e.outcome == Outcome::Completed)
+        .collect();
+    entries.sort_by_key(|e| e.completed_at);
+
+    entries
+        .into_iter()
+        .map(|e| {
+            e.sets
+                .iter()
+                .filter(|s| s.exercise == exercise.name)
+                .collect::<Vec<_>>()
+        })
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// A session is successful when all sets were done and each reached `reps`.
+fn is_successful(exercise: &Exercise, sets: &[&SetLog], reps: Option<u32>) -> bool {
+    sets.len() as u32 >= exercise.sets.unwrap_or(1)
+        && reps.is_none_or(|target| sets.iter().all(|s| s.reps.is_some_and(|r| r >= target)))
+}
+
+fn get_top_load(sets: &[&SetLog]) -> Option<Load> {
+    sets.iter()
+        .filter_map(|s| s.load.clone())
+        .max_by(|a, b| a.amount.total_cmp(&b.amount))
+}
+
+fn round_load(amount: f32, unit: LoadUnit) -> f32 {
+    let step = match unit {
+        LoadUnit::Kg => KG_STEP,
+        LoadUnit::Lb => LB_STEP,
+    };
+
+    (amount / step).round() * step
+}
+
+fn parse_increment(token: &str) -> Option<(f32, Option<LoadUnit>)> {
+    let (amount, unit) = match Load::parse(token) {
+        Some(load) => (load.amount, Some(load.unit)),
+        None => (token.parse::<f32>().ok()?, None),
+    };
+
+    (amount.is_finite() && amount > 0.0).then_some((amount, unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::{HistoryEntry, Outcome, SetLog};
+    use crate::progression::{Progression, Suggestion, suggest};
+    use crate::workout::{Exercise, Load, LoadUnit, Workout};
+    use chrono::{Local, TimeZone};
+
+    fn kg(amount: f32) -> Load {
+        Load {
+            amount,
+            unit: LoadUnit::Kg,
+        }
+    }
+
+    fn create_entry(workout: &Workout, day: u32, sets: &[(u32, f32)]) -> HistoryEntry {
+        let completed_at = Local.with_ymd_and_hms(2025, 9, day, 18, 0, 0).unwrap();
+        let mut entry = HistoryEntry::new(workout, completed_at, Outcome::Completed, None, None);
+        entry.sets = sets
+            .iter()
+            .map(|(reps, amount)| SetLog {
+                exercise: String::from("Squat"),
+                reps: Some(*reps),
+                load: Some(kg(*amount)),
+            })
+            .collect();
+        entry
+    }
+
+    #[test]
+    fn test_parse_should_read_progression_tokens() {
+        let exercise = Exercise::parse("Curl 3x8-12 @10kg +1kg").unwrap();
+
+        assert_eq!("Curl", exercise.name);
+        assert_eq!(Some(8), exercise.reps);
+        assert_eq!(
+            Some(Progression::Double {
+                max_reps: 12,
+                increment: 1.0
+            }),
+            exercise.progression
+        )
+    }
+
+    #[test]
+    fn test_parse_given_increment_in_other_unit_should_not_read_it() {
+        let exercise = Exercise::parse("Squat 5x5 @100kg +5lb").unwrap();
+
+        assert_eq!("Squat 5x5 @100kg +5lb", exercise.name);
+        assert_eq!(None, exercise.progression)
+    }
+
+    #[test]
+    fn test_parse_given_increment_in_training_max_unit_should_read_it() {
+        let exercise = Exercise::parse("Bench 5x5 @135lb 80%tm100kg +2.5kg").unwrap();
+
+        assert_eq!(
+            Some(Progression::TrainingMax {
+                percent: 80.0,
+                max: kg(100.0),
+                increment: Some(2.5)
+            }),
+            exercise.progression
+        )
+    }
+
+    #[test]
+    fn test_parse_given_training_max_should_read_percent_and_max() {
+        let exercise = Exercise::parse("Bench 5x5 80%tm100kg +2.5").unwrap();
+
+        assert_eq!(
+            Some(Progression::TrainingMax {
+                percent: 80.0,
+                max: kg(100.0),
+                increment: Some(2.5)
+            }),
+            exercise.progression
+        )
+    }
+
+    #[test]
+    fn test_suggest_given_linear_and_all_reps_done_should_add_increment() {
+        let workout = Workout::new(String::from("workout1"));
+        let exercise = Exercise::parse("Squat 3x5 @100kg +2.5kg").unwrap();
+        let history = vec![create_entry(
+            &workout,
+            1,
+            &[(5, 100.0), (5, 100.0), (5, 100.0)],
+        )];
+
+        let suggestion = suggest(&exercise, workout.id, &history);
+
+        assert_eq!(
+            Some(Suggestion {
+                reps: Some(5),
+                load: Some(kg(102.5))
+            }),
+            suggestion
+        )
+    }
+
+    #[test]
+    fn test_suggest_given_linear_and_missed_reps_should_repeat_load() {
+        let workout = Workout::new(String::from("workout1"));
+        let exercise = Exercise::parse("Squat 3x5 @100kg +2.5kg").unwrap();
+        let history = vec![create_entry(
+            &workout,
+            1,
+            &[(5, 100.0), (5, 100.0), (3, 100.0)],
+        )];
+
+        let suggestion = suggest(&exercise, workout.id, &history).unwrap();
+
+        assert_eq!(Some(kg(100.0)), suggestion.load)
+    }
+
+    #[test]
+    fn test_suggest_given_double_below_max_should_add_rep() {
+        let workout = Workout::new(String::from("workout1"));
+        let exercise = Exercise::parse("Squat 2x8-12 @60kg +5kg").unwrap();
+        let history = vec![create_entry(&workout, 1, &[(10, 60.0), (9, 60.0)])];
+
+        let suggestion = suggest(&exercise, workout.id, &history).unwrap();
+
+        assert_eq!(
+            Suggestion {
+                reps: Some(10),
+                load: Some(kg(60.0))
+            },
+            suggestion
+        )
+    }
+
+    #[test]
+    fn test_suggest_given_double_at_max_should_add_load_and_reset_reps() {
+        let workout = Workout::new(String::from("workout1"));
+        let exercise = Exercise::parse("Squat 2x8-12 @60kg +5kg").unwrap();
+        let history = vec![create_entry(&workout, 1, &[(12, 60.0), (12, 60.0)])];
+
+        let suggestion = suggest(&exercise, workout.id, &history).unwrap();
+
+        assert_eq!(
+            Suggestion {
+                reps: Some(8),
+                load: Some(kg(65.0))
+            },
+            suggestion
+        )
+    }
+
+    #[test]
+    fn test_suggest_given_training_max_should_round_percentage_and_count_successes() {
+        let workout = Workout::new(String::from("workout1"));
+        let exercise = Exercise::parse("Squat 1x5 75%tm100kg +5kg").unwrap();
+        let history = vec![
+            create_entry(&workout, 1, &[(5, 75.0)]),
+            create_entry(&workout, 3, &[(4, 78.0)]),
+        ];
+
+        let suggestion = suggest(&exercise, workout.id, &history).unwrap();
+
+        // 75% of 105 kg is 78.75 kg
+        assert_eq!(Some(kg(80.0)), suggestion.load)
+    }
+
+    #[test]
+    fn test_suggest_given_no_history_should_return_none_for_linear() {
+        let workout = Workout::new(String::from("workout1"));
+        let exercise = Exercise::parse("Squat 3x5 @100kg +2.5kg").unwrap();
+
+        assert_eq!(None, suggest(&exercise, workout.id, &[]))
+    }
+}
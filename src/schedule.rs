@@ -0,0 +1,251 @@
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// How far ahead the next training day is searched for.
+const LOOKAHEAD_DAYS: u64 = 366;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Every day is a training day, the rotation only moves when a workout is logged.
+    #[default]
+    Off,
+    Weekdays {
+        days: Vec<Weekday>,
+    },
+    /// `on` training days followed by `off` rest days, repeating from `start`.
+    Pattern {
+        on: u32,
+        off: u32,
+        start: NaiveDate,
+    },
+}
+
+impl Schedule {
+    pub fn is_training_day(&self, date: NaiveDate) -> bool {
+        match self {
+            Schedule::Off => true,
+            Schedule::Weekdays { days } => days.contains(&date.weekday()),
+            Schedule::Pattern { on, off, start } => {
+                let period = *on as i64 + *off as i64;
+                if period == 0 {
+                    return false;
+                }
+                let offset = (date - *start).num_days().rem_euclid(period);
+                offset < *on as i64
+            }
+        }
+    }
+
+    pub fn get_next_training_day(&self, from: NaiveDate) -> Option<NaiveDate> {
+        (0..LOOKAHEAD_DAYS)
+            .filter_map(|d| from.checked_add_days(Days::new(d)))
+            .find(|d| self.is_training_day(*d))
+    }
+
+    /// Counts the training days in `from..to`.
+    pub fn count_training_days(&self, from: NaiveDate, to: NaiveDate) -> u32 {
+        from.iter_days()
+            .take_while(|d| *d < to)
+            .filter(|d| self.is_training_day(*d))
+            .count() as u32
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchUpPolicy {
+    /// A missed workout stays due until it is done.
+    #[default]
+    CatchUp,
+    /// Every missed training day moves the rotation on, as if the workout was skipped.
+    Drop,
+}
+
+impl CatchUpPolicy {
+    pub const ALL: [CatchUpPolicy; 2] = [CatchUpPolicy::CatchUp, CatchUpPolicy::Drop];
+}
+
+impl Display for CatchUpPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CatchUpPolicy::CatchUp => write!(f, "Catch up missed days"),
+            CatchUpPolicy::Drop => write!(f, "Drop missed days"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleSettings {
+    #[serde(default)]
+    pub schedule: Schedule,
+    #[serde(default)]
+    pub policy: CatchUpPolicy,
+    /// The day from which the current workout is due.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_from: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Day {
+    Training,
+    Rest { next: Option<NaiveDate> },
+}
+
+impl ScheduleSettings {
+    pub fn is_enabled(&self) -> bool {
+        self.schedule != Schedule::Off
+    }
+
+    pub fn get_day(&self, today: NaiveDate) -> Day {
+        if self.schedule.is_training_day(today) {
+            Day::Training
+        } else {
+            Day::Rest {
+                next: self.schedule.get_next_training_day(today),
+            }
+        }
+    }
+
+    /// Returns how many workouts the rotation has to move on to be back on the calendar.
+    pub fn get_missed_days(&self, today: NaiveDate) -> u32 {
+        match (self.policy, self.due_from) {
+            (CatchUpPolicy::Drop, Some(due_from)) if self.is_enabled() => {
+                self.schedule.count_training_days(due_from, today)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Records when the workout the rotation moved to becomes due.
+    pub fn set_due_from(&mut self, today: NaiveDate, logged: bool) {
+        self.due_from = if !self.is_enabled() {
+            None
+        } else if logged {
+            today.succ_opt()
+        } else {
+            Some(today)
+        };
+    }
+}
+
+pub fn format_day(date: NaiveDate) -> String {
+    date.format("%a, %b %-d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schedule::{CatchUpPolicy, Day, Schedule, ScheduleSettings};
+    use chrono::{NaiveDate, Weekday};
+
+    fn date(day: u32) -> NaiveDate {
+        // 2025-09-01 is a Monday
+        NaiveDate::from_ymd_opt(2025, 9, day).unwrap()
+    }
+
+    fn create_weekdays() -> Schedule {
+        Schedule::Weekdays {
+            days: vec![Weekday::Mon, Weekday::Wed, Weekday::Fri],
+        }
+    }
+
+    #[test]
+    fn test_is_training_day_given_pattern_should_repeat_on_and_off() {
+        let schedule = Schedule::Pattern {
+            on: 2,
+            off: 1,
+            start: date(1),
+        };
+
+        let days: Vec<bool> = (1..=6).map(|d| schedule.is_training_day(date(d))).collect();
+
+        assert_eq!(vec![true, true, false, true, true, false], days)
+    }
+
+    #[test]
+    fn test_is_training_day_given_date_before_pattern_start_should_continue_pattern() {
+        let schedule = Schedule::Pattern {
+            on: 2,
+            off: 1,
+            start: date(4),
+        };
+
+        assert!(!schedule.is_training_day(date(3)))
+    }
+
+    #[test]
+    fn test_is_training_day_given_huge_pattern_should_not_overflow() {
+        let schedule = Schedule::Pattern {
+            on: u32::MAX,
+            off: u32::MAX,
+            start: date(1),
+        };
+
+        assert!(schedule.is_training_day(date(2)))
+    }
+
+    #[test]
+    fn test_get_next_training_day_given_weekend_should_return_monday() {
+        let next = create_weekdays().get_next_training_day(date(6));
+
+        assert_eq!(Some(date(8)), next)
+    }
+
+    #[test]
+    fn test_get_next_training_day_given_no_training_days_should_return_none() {
+        let schedule = Schedule::Weekdays { days: vec![] };
+
+        assert_eq!(None, schedule.get_next_training_day(date(1)))
+    }
+
+    #[test]
+    fn test_get_day_given_rest_day_should_return_next_training_day() {
+        let settings = ScheduleSettings {
+            schedule: create_weekdays(),
+            ..ScheduleSettings::default()
+        };
+
+        assert_eq!(
+            Day::Rest {
+                next: Some(date(3))
+            },
+            settings.get_day(date(2))
+        )
+    }
+
+    #[test]
+    fn test_get_missed_days_given_drop_policy_should_count_training_days_before_today() {
+        let settings = ScheduleSettings {
+            schedule: create_weekdays(),
+            policy: CatchUpPolicy::Drop,
+            due_from: Some(date(1)),
+        };
+
+        assert_eq!(3, settings.get_missed_days(date(8)))
+    }
+
+    #[test]
+    fn test_get_missed_days_given_catch_up_policy_should_be_zero() {
+        let settings = ScheduleSettings {
+            schedule: create_weekdays(),
+            policy: CatchUpPolicy::CatchUp,
+            due_from: Some(date(1)),
+        };
+
+        assert_eq!(0, settings.get_missed_days(date(8)))
+    }
+
+    #[test]
+    fn test_set_due_from_given_logged_workout_should_be_due_tomorrow() {
+        let mut settings = ScheduleSettings {
+            schedule: create_weekdays(),
+            ..ScheduleSettings::default()
+        };
+
+        settings.set_due_from(date(1), true);
+
+        assert_eq!(Some(date(2)), settings.due_from)
+    }
+}
@@ -0,0 +1,137 @@
+use crate::workout::Workout;
+
+const CONSECUTIVE_BONUS: i32 = 5;
+const WORD_START_BONUS: i32 = 3;
+/// Matches in the text rank above matches in tags, which rank above matches in notes.
+const TAG_PENALTY: i32 = 2;
+const NOTES_PENALTY: i32 = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    /// Char indices of the matched characters, ascending.
+    pub positions: Vec<usize>,
+}
+
+/// Finds the characters of the query in order within the text, ignoring case and whitespace
+/// in the query. Runs of consecutive characters and matches at word starts score higher,
+/// gaps lower.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(to_lower)
+        .collect();
+    let text: Vec<char> = text.chars().collect();
+    let first = *query.first()?;
+
+    // reversed so that the earliest of equally good matches wins
+    (0..text.len())
+        .rev()
+        .filter(|&start| to_lower(text[start]) == first)
+        .filter_map(|start| match_from(&query, &text, start))
+        .max_by_key(|m| m.score)
+}
+
+/// Matches a workout by its text, tags and notes. Only matches in the text carry positions,
+/// since only the text is shown in the list.
+pub fn search_workout(query: &str, workout: &Workout) -> Option<FuzzyMatch> {
+    let text = fuzzy_match(query, &workout.text);
+    let tags = workout
+        .tags
+        .iter()
+        .filter_map(|t| fuzzy_match(query, t))
+        .map(|m| m.score - TAG_PENALTY)
+        .max();
+    let notes = fuzzy_match(query, &workout.notes).map(|m| m.score - NOTES_PENALTY);
+
+    match (text, tags.max(notes)) {
+        (Some(text), Some(other)) if other > text.score => Some(FuzzyMatch {
+            score: other,
+            ..text
+        }),
+        (Some(text), _) => Some(text),
+        (None, other) => other.map(|score| FuzzyMatch {
+            score,
+            positions: vec![],
+        }),
+    }
+}
+
+fn match_from(query: &[char], text: &[char], start: usize) -> Option<FuzzyMatch> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0;
+    let mut next = start;
+    for q in query {
+        let position = (next..text.len()).find(|&i| to_lower(text[i]) == *q)?;
+        score += 1;
+        match positions.last() {
+            Some(&last) if position == last + 1 => score += CONSECUTIVE_BONUS,
+            Some(&last) => score -= (position - last - 1) as i32,
+            None => {}
+        }
+        if position == 0 || !text[position - 1].is_alphanumeric() {
+            score += WORD_START_BONUS;
+        }
+        positions.push(position);
+        next = position + 1;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+fn to_lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::{fuzzy_match, search_workout};
+    use crate::workout::Workout;
+
+    #[test]
+    fn test_fuzzy_match_given_subsequence_should_return_positions() {
+        let found = fuzzy_match("upd", "Upper Day").unwrap();
+
+        assert_eq!(vec![0, 1, 6], found.positions)
+    }
+
+    #[test]
+    fn test_fuzzy_match_given_missing_character_should_be_none() {
+        assert_eq!(None, fuzzy_match("lgz", "Legs"))
+    }
+
+    #[test]
+    fn test_fuzzy_match_given_blank_query_should_be_none() {
+        assert_eq!(None, fuzzy_match("  ", "Legs"))
+    }
+
+    #[test]
+    fn test_fuzzy_match_should_prefer_consecutive_word_start() {
+        let found = fuzzy_match("pu", "Pull up Push").unwrap();
+
+        assert_eq!(vec![0, 1], found.positions);
+        assert!(found.score > fuzzy_match("pu", "Pxxu").unwrap().score)
+    }
+
+    #[test]
+    fn test_search_workout_given_tag_match_should_match_without_positions() {
+        let mut workout = Workout::new(String::from("Day A"));
+        workout.tags = vec![String::from("cardio")];
+
+        let found = search_workout("card", &workout).unwrap();
+
+        assert!(found.positions.is_empty())
+    }
+
+    #[test]
+    fn test_search_workout_given_notes_match_should_rank_below_text_match() {
+        let mut by_notes = Workout::new(String::from("Day A"));
+        by_notes.notes = String::from("Legs and core");
+        let by_text = Workout::new(String::from("Legs"));
+
+        let notes_score = search_workout("legs", &by_notes).unwrap().score;
+
+        assert!(search_workout("legs", &by_text).unwrap().score > notes_score)
+    }
+}
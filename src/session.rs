@@ -0,0 +1,160 @@
+use crate::history::SetLog;
+use crate::workout::{Exercise, Load};
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+/// One set of an exercise. Exercises without a set count are done once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub exercise: usize,
+    pub set: u32,
+}
+
+/// Walks through the sets of a workout and collects what was actually done.
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// The workout the session was started for, which stays the same if the rotation moves.
+    pub workout_id: Uuid,
+    pub exercises: Vec<Exercise>,
+    pub logs: Vec<SetLog>,
+    pub started_at: DateTime<Local>,
+    steps: Vec<Step>,
+    position: usize,
+}
+
+impl Session {
+    pub fn new(workout_id: Uuid, exercises: Vec<Exercise>, started_at: DateTime<Local>) -> Session {
+        let steps = exercises
+            .iter()
+            .enumerate()
+            .flat_map(|(exercise, e)| {
+                (1..=e.sets.unwrap_or(1).max(1)).map(move |set| Step { exercise, set })
+            })
+            .collect();
+
+        Session {
+            workout_id,
+            exercises,
+            logs: vec![],
+            started_at,
+            steps,
+            position: 0,
+        }
+    }
+
+    pub fn get_step(&self) -> Option<Step> {
+        self.steps.get(self.position).copied()
+    }
+
+    pub fn get_exercise(&self) -> Option<&Exercise> {
+        self.get_step().map(|s| &self.exercises[s.exercise])
+    }
+
+    /// Returns the number of the current step and the number of steps.
+    pub fn get_progress(&self) -> (usize, usize) {
+        (self.position + 1, self.steps.len())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.steps.len()
+    }
+
+    /// Records the current set and moves on. Returns whether another set follows.
+    pub fn log_set(&mut self, reps: Option<u32>, load: Option<Load>) -> bool {
+        if let Some(exercise) = self.get_exercise() {
+            self.logs.push(SetLog {
+                exercise: exercise.name.clone(),
+                reps,
+                load,
+            });
+        }
+
+        self.skip_set()
+    }
+
+    /// Moves on without recording the current set. Returns whether another set follows.
+    pub fn skip_set(&mut self) -> bool {
+        self.position = (self.position + 1).min(self.steps.len());
+
+        !self.is_finished()
+    }
+
+    pub fn get_duration_secs(&self, now: DateTime<Local>) -> Option<u32> {
+        u32::try_from((now - self.started_at).num_seconds()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::session::{Session, Step};
+    use crate::workout::Exercise;
+    use chrono::{Duration, Local, TimeZone};
+    use uuid::Uuid;
+
+    fn create_session(lines: &[&str]) -> Session {
+        let exercises = lines.iter().filter_map(|l| Exercise::parse(l)).collect();
+        let started_at = Local.with_ymd_and_hms(2025, 9, 1, 18, 0, 0).unwrap();
+
+        Session::new(Uuid::new_v4(), exercises, started_at)
+    }
+
+    #[test]
+    fn test_new_should_create_step_per_set() {
+        let session = create_session(&["Squat 3x5", "Plank 60s"]);
+
+        assert_eq!((1, 4), session.get_progress())
+    }
+
+    #[test]
+    fn test_log_set_given_last_set_of_exercise_should_move_to_next_exercise() {
+        let mut session = create_session(&["Squat 2x5", "Plank 60s"]);
+        session.log_set(Some(5), None);
+
+        let has_next = session.log_set(Some(4), None);
+
+        assert!(has_next);
+        assert_eq!(
+            Some(Step {
+                exercise: 1,
+                set: 1
+            }),
+            session.get_step()
+        )
+    }
+
+    #[test]
+    fn test_log_set_given_last_set_should_finish() {
+        let mut session = create_session(&["Squat 1x5"]);
+
+        let has_next = session.log_set(Some(5), None);
+
+        assert!(!has_next);
+        assert!(session.is_finished());
+        assert_eq!(1, session.logs.len())
+    }
+
+    #[test]
+    fn test_skip_set_should_not_log() {
+        let mut session = create_session(&["Squat 2x5"]);
+
+        session.skip_set();
+
+        assert!(session.logs.is_empty());
+        assert_eq!(
+            Some(Step {
+                exercise: 0,
+                set: 2
+            }),
+            session.get_step()
+        )
+    }
+
+    #[test]
+    fn test_get_duration_secs_should_count_from_start() {
+        let session = create_session(&["Squat 2x5"]);
+
+        let secs = session.get_duration_secs(session.started_at + Duration::minutes(45));
+
+        assert_eq!(Some(2700), secs)
+    }
+}
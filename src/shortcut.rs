@@ -0,0 +1,103 @@
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+
+/// Keys the app reacts to. What a shortcut does depends on the page and dialog it is pressed on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shortcut {
+    Next,
+    Previous,
+    Skip,
+    Up,
+    Down,
+    MoveUp,
+    MoveDown,
+    Delete,
+    Confirm,
+    Back,
+    Settings,
+    History,
+    Undo,
+    Redo,
+    Help,
+    Timer,
+}
+
+/// Key and description pairs listed in the help overlay.
+pub const SHORTCUTS: [(&str, &str); 13] = [
+    ("\u{2192} / N", "Complete current workout"),
+    ("\u{2190} / P", "Back to previous workout"),
+    ("K", "Skip current workout"),
+    ("S / H", "Open settings / history"),
+    ("T", "Start / pause timer"),
+    ("\u{2191} / \u{2193}", "Select workout in list"),
+    ("Alt+\u{2191} / Alt+\u{2193}", "Move selected workout"),
+    ("Delete", "Remove selected workout"),
+    ("Ctrl+Z", "Undo"),
+    ("Ctrl+Shift+Z / Ctrl+Y", "Redo"),
+    ("Enter", "Confirm dialog"),
+    ("Escape", "Cancel dialog / go back"),
+    ("? / F1", "Toggle this help"),
+];
+
+pub fn get_shortcut(key: Key, modifiers: Modifiers) -> Option<Shortcut> {
+    match key.as_ref() {
+        Key::Named(Named::ArrowUp) if modifiers.alt() => Some(Shortcut::MoveUp),
+        Key::Named(Named::ArrowDown) if modifiers.alt() => Some(Shortcut::MoveDown),
+        Key::Named(Named::ArrowUp) => Some(Shortcut::Up),
+        Key::Named(Named::ArrowDown) => Some(Shortcut::Down),
+        Key::Named(Named::ArrowRight) => Some(Shortcut::Next),
+        Key::Named(Named::ArrowLeft) => Some(Shortcut::Previous),
+        Key::Named(Named::Delete) => Some(Shortcut::Delete),
+        Key::Named(Named::Enter) => Some(Shortcut::Confirm),
+        Key::Named(Named::Escape) => Some(Shortcut::Back),
+        Key::Named(Named::F1) => Some(Shortcut::Help),
+        Key::Character(c) if modifiers.command() => match c.to_lowercase().as_str() {
+            "z" if modifiers.shift() => Some(Shortcut::Redo),
+            "z" => Some(Shortcut::Undo),
+            "y" => Some(Shortcut::Redo),
+            _ => None,
+        },
+        Key::Character(c) if !modifiers.alt() => match c.to_lowercase().as_str() {
+            "n" => Some(Shortcut::Next),
+            "p" => Some(Shortcut::Previous),
+            "k" => Some(Shortcut::Skip),
+            "s" => Some(Shortcut::Settings),
+            "h" => Some(Shortcut::History),
+            "t" => Some(Shortcut::Timer),
+            "?" => Some(Shortcut::Help),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shortcut::{Shortcut, get_shortcut};
+    use iced::keyboard::key::Named;
+    use iced::keyboard::{Key, Modifiers};
+
+    #[test]
+    fn test_get_shortcut_given_alt_arrow_should_move() {
+        let shortcut = get_shortcut(Key::Named(Named::ArrowUp), Modifiers::ALT);
+
+        assert_eq!(Some(Shortcut::MoveUp), shortcut)
+    }
+
+    #[test]
+    fn test_get_shortcut_given_ctrl_shift_z_should_redo() {
+        let shortcut = get_shortcut(
+            Key::Character("Z".into()),
+            Modifiers::COMMAND | Modifiers::SHIFT,
+        );
+
+        assert_eq!(Some(Shortcut::Redo), shortcut)
+    }
+
+    #[test]
+    fn test_get_shortcut_given_ctrl_s_should_return_none() {
+        let shortcut = get_shortcut(Key::Character("s".into()), Modifiers::COMMAND);
+
+        assert_eq!(None, shortcut)
+    }
+}
@@ -0,0 +1,304 @@
+use crate::history::{HistoryEntry, Outcome};
+use crate::schedule::Schedule;
+use crate::workout::{Load, LoadUnit, Workout};
+use chrono::{Datelike, Days, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet};
+
+const KG_PER_LB: f32 = 0.453_592_37;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Streaks {
+    pub current: u32,
+    pub longest: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekCount {
+    /// The Monday the week starts on.
+    pub start: NaiveDate,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkoutRate {
+    pub workout_text: String,
+    pub completed: u32,
+    pub skipped: u32,
+}
+
+impl WorkoutRate {
+    /// Share of completions among all logged entries, zero without entries.
+    pub fn get_rate(&self) -> f32 {
+        let total = self.completed + self.skipped;
+        if total == 0 {
+            return 0.0;
+        }
+
+        self.completed as f32 / total as f32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumePoint {
+    pub date: NaiveDate,
+    /// Sum of reps times load over all sets of the day, in kg.
+    pub volume: f32,
+    /// Heaviest load lifted that day, in kg.
+    pub top_load: f32,
+}
+
+/// Counts consecutive training days with a completed workout, so rest days of the schedule
+/// neither extend nor break a streak. The current streak is still alive while today is open.
+pub fn get_streaks(entries: &[HistoryEntry], schedule: &Schedule, today: NaiveDate) -> Streaks {
+    let dates: BTreeSet<NaiveDate> = entries
+        .iter()
+        .filter(|e| e.outcome == Outcome::Completed)
+        .map(|e| e.completed_at.date_naive())
+        .collect();
+    let first = match dates.first() {
+        None => return Streaks::default(),
+        Some(d) => *d,
+    };
+
+    let done: Vec<bool> = first
+        .iter_days()
+        .take_while(|d| *d <= today)
+        .filter(|d| schedule.is_training_day(*d))
+        .map(|d| dates.contains(&d))
+        .collect();
+
+    let mut longest = 0;
+    let mut run = 0;
+    for is_done in &done {
+        run = if *is_done { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+
+    let is_today_open = schedule.is_training_day(today) && !dates.contains(&today);
+    let current = done
+        .iter()
+        .rev()
+        .skip(is_today_open as usize)
+        .take_while(|d| **d)
+        .count() as u32;
+
+    Streaks { current, longest }
+}
+
+/// Counts completed workouts per week for the last `weeks` weeks, oldest first.
+pub fn count_sessions_per_week(
+    entries: &[HistoryEntry],
+    today: NaiveDate,
+    weeks: u32,
+) -> Vec<WeekCount> {
+    let this_week = today - Days::new(today.weekday().num_days_from_monday() as u64);
+
+    (0..weeks)
+        .rev()
+        .filter_map(|i| this_week.checked_sub_days(Days::new(7 * i as u64)))
+        .map(|start| WeekCount {
+            start,
+            count: entries
+                .iter()
+                .filter(|e| e.outcome == Outcome::Completed)
+                .map(|e| e.completed_at.date_naive())
+                .filter(|d| *d >= start && (*d - start).num_days() < 7)
+                .count() as u32,
+        })
+        .collect()
+}
+
+/// Returns completions and skips for every workout of the rotation, in rotation order.
+pub fn get_workout_rates(entries: &[HistoryEntry], workouts: &[Workout]) -> Vec<WorkoutRate> {
+    workouts
+        .iter()
+        .map(|w| {
+            let count = |outcome: Outcome| {
+                entries
+                    .iter()
+                    .filter(|e| e.workout_id == w.id && e.outcome == outcome)
+                    .count() as u32
+            };
+
+            WorkoutRate {
+                workout_text: w.text.clone(),
+                completed: count(Outcome::Completed),
+                skipped: count(Outcome::Skipped),
+            }
+        })
+        .collect()
+}
+
+/// Sums the logged sets per day. Days without sets that carry both reps and load are left out.
+pub fn get_volume_trend(entries: &[HistoryEntry]) -> Vec<VolumePoint> {
+    let mut days: BTreeMap<NaiveDate, VolumePoint> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.outcome == Outcome::Completed) {
+        let date = entry.completed_at.date_naive();
+        for set in &entry.sets {
+            let (Some(reps), Some(load)) = (set.reps, &set.load) else {
+                continue;
+            };
+
+            let kg = to_kg(load);
+            let point = days.entry(date).or_insert(VolumePoint {
+                date,
+                volume: 0.0,
+                top_load: 0.0,
+            });
+            point.volume += reps as f32 * kg;
+            point.top_load = point.top_load.max(kg);
+        }
+    }
+
+    days.into_values().collect()
+}
+
+fn to_kg(load: &Load) -> f32 {
+    match load.unit {
+        LoadUnit::Kg => load.amount,
+        LoadUnit::Lb => load.amount * KG_PER_LB,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::{HistoryEntry, Outcome, SetLog};
+    use crate::schedule::Schedule;
+    use crate::stats::{
+        Streaks, WeekCount, count_sessions_per_week, get_streaks, get_volume_trend,
+        get_workout_rates,
+    };
+    use crate::workout::{Load, LoadUnit, Workout};
+    use chrono::{Local, NaiveDate, TimeZone, Weekday};
+
+    fn date(day: u32) -> NaiveDate {
+        // 2025-09-01 is a Monday
+        NaiveDate::from_ymd_opt(2025, 9, day).unwrap()
+    }
+
+    fn create_entry(workout: &Workout, day: u32, outcome: Outcome) -> HistoryEntry {
+        let completed_at = Local.with_ymd_and_hms(2025, 9, day, 18, 0, 0).unwrap();
+
+        HistoryEntry::new(workout, completed_at, outcome, None, None)
+    }
+
+    fn create_set(reps: u32, amount: f32, unit: LoadUnit) -> SetLog {
+        SetLog {
+            exercise: String::from("Squat"),
+            reps: Some(reps),
+            load: Some(Load { amount, unit }),
+        }
+    }
+
+    #[test]
+    fn test_get_streaks_given_gap_should_track_current_and_longest() {
+        let workout = Workout::new(String::from("workout1"));
+        let entries: Vec<_> = [1, 2, 3, 5, 6]
+            .into_iter()
+            .map(|d| create_entry(&workout, d, Outcome::Completed))
+            .collect();
+
+        let streaks = get_streaks(&entries, &Schedule::Off, date(7));
+
+        assert_eq!(
+            Streaks {
+                current: 2,
+                longest: 3
+            },
+            streaks
+        )
+    }
+
+    #[test]
+    fn test_get_streaks_given_schedule_should_skip_rest_days() {
+        let workout = Workout::new(String::from("workout1"));
+        let entries: Vec<_> = [1, 3, 5, 8]
+            .into_iter()
+            .map(|d| create_entry(&workout, d, Outcome::Completed))
+            .collect();
+        let schedule = Schedule::Weekdays {
+            days: vec![Weekday::Mon, Weekday::Wed, Weekday::Fri],
+        };
+
+        let streaks = get_streaks(&entries, &schedule, date(10));
+
+        assert_eq!(
+            Streaks {
+                current: 4,
+                longest: 4
+            },
+            streaks
+        )
+    }
+
+    #[test]
+    fn test_get_streaks_given_skips_only_should_be_zero() {
+        let workout = Workout::new(String::from("workout1"));
+        let entries = vec![create_entry(&workout, 1, Outcome::Skipped)];
+
+        assert_eq!(
+            Streaks::default(),
+            get_streaks(&entries, &Schedule::Off, date(1))
+        )
+    }
+
+    #[test]
+    fn test_count_sessions_per_week_should_bucket_by_monday() {
+        let workout = Workout::new(String::from("workout1"));
+        let entries = vec![
+            create_entry(&workout, 1, Outcome::Completed),
+            create_entry(&workout, 7, Outcome::Completed),
+            create_entry(&workout, 8, Outcome::Completed),
+            create_entry(&workout, 9, Outcome::Skipped),
+        ];
+
+        let weeks = count_sessions_per_week(&entries, date(10), 2);
+
+        assert_eq!(
+            vec![
+                WeekCount {
+                    start: date(1),
+                    count: 2
+                },
+                WeekCount {
+                    start: date(8),
+                    count: 1
+                },
+            ],
+            weeks
+        )
+    }
+
+    #[test]
+    fn test_get_workout_rates_should_count_per_workout() {
+        let workout1 = Workout::new(String::from("workout1"));
+        let workout2 = Workout::new(String::from("workout2"));
+        let entries = vec![
+            create_entry(&workout1, 1, Outcome::Completed),
+            create_entry(&workout1, 2, Outcome::Completed),
+            create_entry(&workout1, 3, Outcome::Completed),
+            create_entry(&workout1, 4, Outcome::Skipped),
+        ];
+
+        let rates = get_workout_rates(&entries, &[workout1, workout2]);
+
+        assert_eq!(0.75, rates[0].get_rate());
+        assert_eq!(0.0, rates[1].get_rate())
+    }
+
+    #[test]
+    fn test_get_volume_trend_should_sum_sets_per_day_in_kg() {
+        let workout = Workout::new(String::from("workout1"));
+        let mut entry = create_entry(&workout, 1, Outcome::Completed);
+        entry.sets = vec![
+            create_set(5, 100.0, LoadUnit::Kg),
+            create_set(10, 100.0, LoadUnit::Lb),
+        ];
+
+        let trend = get_volume_trend(&[entry]);
+
+        assert_eq!(1, trend.len());
+        assert!((trend[0].volume - 953.59).abs() < 0.01);
+        assert_eq!(100.0, trend[0].top_load)
+    }
+}
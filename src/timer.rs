@@ -0,0 +1,410 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// How long a transition stays highlighted after it happened.
+const CUE_DURATION: Duration = Duration::from_secs(1);
+const TABATA_ROUNDS: u32 = 8;
+const TABATA_WORK: Duration = Duration::from_secs(20);
+const TABATA_REST: Duration = Duration::from_secs(10);
+const EMOM_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TimerMode {
+    #[default]
+    Stopwatch,
+    Rest,
+    Emom,
+    Tabata,
+    Amrap,
+}
+
+impl TimerMode {
+    pub const ALL: [TimerMode; 5] = [
+        TimerMode::Stopwatch,
+        TimerMode::Rest,
+        TimerMode::Emom,
+        TimerMode::Tabata,
+        TimerMode::Amrap,
+    ];
+
+    /// Describes what the amount of the mode counts, `None` if the mode takes no amount.
+    pub fn get_amount_unit(&self) -> Option<&'static str> {
+        match self {
+            TimerMode::Rest => Some("sec"),
+            TimerMode::Emom => Some("rounds"),
+            TimerMode::Amrap => Some("min"),
+            TimerMode::Stopwatch | TimerMode::Tabata => None,
+        }
+    }
+
+    pub fn get_default_amount(&self) -> u32 {
+        match self {
+            TimerMode::Rest => 90,
+            TimerMode::Emom => 10,
+            TimerMode::Amrap => 12,
+            TimerMode::Stopwatch | TimerMode::Tabata => 0,
+        }
+    }
+
+    /// Bounds the amount, since EMOM builds one phase per round.
+    pub fn get_max_amount(&self) -> u32 {
+        match self {
+            TimerMode::Rest => 3600,
+            TimerMode::Emom => 99,
+            TimerMode::Amrap => 180,
+            TimerMode::Stopwatch | TimerMode::Tabata => 0,
+        }
+    }
+
+    pub fn get_phases(&self, amount: u32) -> Vec<Phase> {
+        match self {
+            TimerMode::Stopwatch => vec![Phase::new(PhaseKind::Work, None)],
+            TimerMode::Rest => vec![Phase::new(
+                PhaseKind::Rest,
+                Some(Duration::from_secs(amount as u64)),
+            )],
+            TimerMode::Emom => (0..amount)
+                .map(|_| Phase::new(PhaseKind::Work, Some(EMOM_INTERVAL)))
+                .collect(),
+            TimerMode::Tabata => (0..TABATA_ROUNDS)
+                .flat_map(|_| {
+                    [
+                        Phase::new(PhaseKind::Work, Some(TABATA_WORK)),
+                        Phase::new(PhaseKind::Rest, Some(TABATA_REST)),
+                    ]
+                })
+                .collect(),
+            TimerMode::Amrap => vec![Phase::new(
+                PhaseKind::Work,
+                Some(Duration::from_secs(amount as u64 * 60)),
+            )],
+        }
+    }
+}
+
+impl Display for TimerMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerMode::Stopwatch => write!(f, "Stopwatch"),
+            TimerMode::Rest => write!(f, "Rest"),
+            TimerMode::Emom => write!(f, "EMOM"),
+            TimerMode::Tabata => write!(f, "Tabata"),
+            TimerMode::Amrap => write!(f, "AMRAP"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhaseKind {
+    Work,
+    Rest,
+}
+
+impl Display for PhaseKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PhaseKind::Work => write!(f, "Work"),
+            PhaseKind::Rest => write!(f, "Rest"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Phase {
+    pub kind: PhaseKind,
+    /// `None` runs until the timer is stopped.
+    pub duration: Option<Duration>,
+}
+
+impl Phase {
+    pub fn new(kind: PhaseKind, duration: Option<Duration>) -> Phase {
+        Phase { kind, duration }
+    }
+}
+
+/// Signals a transition the user should notice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cue {
+    Phase(PhaseKind),
+    Finished,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimerStatus {
+    /// `None` once all phases are over.
+    pub kind: Option<PhaseKind>,
+    /// Time left in the current phase, `None` for open phases.
+    pub remaining: Option<Duration>,
+    pub elapsed: Duration,
+    pub round: usize,
+    pub rounds: usize,
+}
+
+/// Runs through a list of phases. The timer does not read the clock itself, every call
+/// that depends on time is handed the current instant.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    phases: Vec<Phase>,
+    running_since: Option<Instant>,
+    /// Time elapsed before the current run.
+    banked: Duration,
+    /// Time elapsed as of the last tick.
+    elapsed: Duration,
+    phase: Option<usize>,
+    last_tick: Option<Instant>,
+    last_cue: Option<(Cue, Instant)>,
+}
+
+impl Default for Timer {
+    fn default() -> Timer {
+        Timer::new(TimerMode::default().get_phases(0))
+    }
+}
+
+impl Timer {
+    pub fn new(phases: Vec<Phase>) -> Timer {
+        let phase = (!phases.is_empty()).then_some(0);
+
+        Timer {
+            phases,
+            running_since: None,
+            banked: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            phase,
+            last_tick: None,
+            last_cue: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.phase.is_none()
+    }
+
+    /// Whether a transition happened within a second of the last tick.
+    pub fn is_cued(&self) -> bool {
+        match (self.last_cue, self.last_tick) {
+            (Some((_, at)), Some(now)) => now.saturating_duration_since(at) < CUE_DURATION,
+            _ => false,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        if !self.is_running() && !self.is_finished() {
+            self.banked = self.elapsed;
+            self.running_since = Some(now);
+        }
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        if self.is_running() {
+            self.tick(now);
+            self.banked = self.elapsed;
+            self.running_since = None;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Timer::new(self.phases.clone());
+    }
+
+    /// Moves the timer on to `now` and returns the cue of the last transition crossed.
+    pub fn tick(&mut self, now: Instant) -> Option<Cue> {
+        self.last_tick = Some(now);
+        let since = self.running_since?;
+        self.elapsed = self.banked + now.saturating_duration_since(since);
+
+        let phase = self.locate(self.elapsed).map(|(i, _)| i);
+        if phase == self.phase {
+            return None;
+        }
+
+        let cue = match phase {
+            Some(i) => Cue::Phase(self.phases[i].kind),
+            None => {
+                self.elapsed = self.get_total().unwrap_or(self.elapsed);
+                self.banked = self.elapsed;
+                self.running_since = None;
+                Cue::Finished
+            }
+        };
+        self.phase = phase;
+        self.last_cue = Some((cue, now));
+
+        Some(cue)
+    }
+
+    pub fn get_status(&self) -> TimerStatus {
+        let rounds = self.count_work_phases(self.phases.len());
+        let located = self.locate(self.elapsed);
+
+        TimerStatus {
+            kind: located.map(|(i, _)| self.phases[i].kind),
+            remaining: located
+                .and_then(|(i, into)| self.phases[i].duration.map(|d| d.saturating_sub(into))),
+            elapsed: self.elapsed,
+            round: located.map_or(rounds, |(i, _)| self.count_work_phases(i + 1)),
+            rounds,
+        }
+    }
+
+    /// Returns the phase running at `elapsed` and how far into it the timer is.
+    fn locate(&self, elapsed: Duration) -> Option<(usize, Duration)> {
+        let mut start = Duration::ZERO;
+        for (i, phase) in self.phases.iter().enumerate() {
+            match phase.duration {
+                None => return Some((i, elapsed - start)),
+                Some(duration) if elapsed < start + duration => {
+                    return Some((i, elapsed - start));
+                }
+                Some(duration) => start += duration,
+            }
+        }
+
+        None
+    }
+
+    fn get_total(&self) -> Option<Duration> {
+        self.phases.iter().map(|p| p.duration).sum()
+    }
+
+    fn count_work_phases(&self, end: usize) -> usize {
+        self.phases[..end]
+            .iter()
+            .filter(|p| p.kind == PhaseKind::Work)
+            .count()
+    }
+}
+
+/// Formats as `m:ss`, or `h:mm:ss` from one hour on. Partial seconds are rounded up so
+/// that a countdown shows zero only once it is over.
+pub fn format_duration(duration: Duration) -> String {
+    let mut secs = duration.as_secs();
+    if duration.subsec_nanos() > 0 {
+        secs += 1;
+    }
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timer::{Cue, PhaseKind, Timer, TimerMode, format_duration};
+    use std::time::{Duration, Instant};
+
+    /// Hands out instants relative to a fixed start, so tests never wait for real time.
+    struct FakeClock {
+        start: Instant,
+    }
+
+    impl FakeClock {
+        fn new() -> FakeClock {
+            FakeClock {
+                start: Instant::now(),
+            }
+        }
+
+        fn at(&self, secs: u64) -> Instant {
+            self.start + Duration::from_secs(secs)
+        }
+    }
+
+    fn create_timer(mode: TimerMode, amount: u32) -> Timer {
+        Timer::new(mode.get_phases(amount))
+    }
+
+    #[test]
+    fn test_tick_given_rest_countdown_should_report_remaining_time() {
+        let clock = FakeClock::new();
+        let mut timer = create_timer(TimerMode::Rest, 90);
+        timer.start(clock.at(0));
+
+        timer.tick(clock.at(30));
+
+        assert_eq!(Some(Duration::from_secs(60)), timer.get_status().remaining)
+    }
+
+    #[test]
+    fn test_tick_given_tabata_work_phase_over_should_cue_rest() {
+        let clock = FakeClock::new();
+        let mut timer = create_timer(TimerMode::Tabata, 0);
+        timer.start(clock.at(0));
+
+        assert_eq!(None, timer.tick(clock.at(19)));
+        assert_eq!(Some(Cue::Phase(PhaseKind::Rest)), timer.tick(clock.at(20)));
+        assert!(timer.is_cued())
+    }
+
+    #[test]
+    fn test_tick_given_last_phase_over_should_finish_and_stop() {
+        let clock = FakeClock::new();
+        let mut timer = create_timer(TimerMode::Emom, 2);
+        timer.start(clock.at(0));
+
+        let cue = timer.tick(clock.at(125));
+
+        assert_eq!(Some(Cue::Finished), cue);
+        assert!(!timer.is_running());
+        assert_eq!(Duration::from_secs(120), timer.elapsed())
+    }
+
+    #[test]
+    fn test_tick_given_paused_timer_should_not_count_pause() {
+        let clock = FakeClock::new();
+        let mut timer = create_timer(TimerMode::Stopwatch, 0);
+        timer.start(clock.at(0));
+        timer.pause(clock.at(10));
+
+        timer.start(clock.at(100));
+        timer.tick(clock.at(105));
+
+        assert_eq!(Duration::from_secs(15), timer.elapsed())
+    }
+
+    #[test]
+    fn test_is_cued_given_second_passed_should_be_false() {
+        let clock = FakeClock::new();
+        let mut timer = create_timer(TimerMode::Emom, 3);
+        timer.start(clock.at(0));
+        timer.tick(clock.at(60));
+
+        timer.tick(clock.at(61));
+
+        assert!(!timer.is_cued())
+    }
+
+    #[test]
+    fn test_get_status_given_emom_should_count_rounds() {
+        let clock = FakeClock::new();
+        let mut timer = create_timer(TimerMode::Emom, 10);
+        timer.start(clock.at(0));
+
+        timer.tick(clock.at(150));
+
+        let status = timer.get_status();
+        assert_eq!((3, 10), (status.round, status.rounds))
+    }
+
+    #[test]
+    fn test_format_duration_given_partial_second_should_round_up() {
+        assert_eq!("1:05", format_duration(Duration::from_millis(64_200)))
+    }
+
+    #[test]
+    fn test_format_duration_given_hours_should_pad_minutes() {
+        assert_eq!("1:02:03", format_duration(Duration::from_secs(3723)))
+    }
+}
@@ -0,0 +1,488 @@
+use crate::persistence::write_atomically;
+use crate::workout::{Workout, format_exercises, is_valid_workout_text, parse_exercises};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::{fmt, fs, io};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferFormat {
+    Csv,
+    Json,
+    Text,
+}
+
+impl TransferFormat {
+    pub const ALL: [TransferFormat; 3] = [
+        TransferFormat::Csv,
+        TransferFormat::Json,
+        TransferFormat::Text,
+    ];
+
+    pub fn from_path(path: &Path) -> Option<TransferFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(TransferFormat::Csv),
+            "json" => Some(TransferFormat::Json),
+            "txt" | "text" => Some(TransferFormat::Text),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TransferFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferFormat::Csv => write!(f, "CSV"),
+            TransferFormat::Json => write!(f, "JSON"),
+            TransferFormat::Text => write!(f, "Text"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MergeMode {
+    /// Adds the workouts that are not yet part of the rotation.
+    #[default]
+    Append,
+    /// Replaces the rotation, including the index if the file carries one.
+    Replace,
+    /// Like append, but workouts already in the rotation take the imported exercises.
+    Merge,
+}
+
+impl MergeMode {
+    pub const ALL: [MergeMode; 3] = [MergeMode::Append, MergeMode::Replace, MergeMode::Merge];
+}
+
+impl Display for MergeMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeMode::Append => write!(f, "Append new"),
+            MergeMode::Replace => write!(f, "Replace all"),
+            MergeMode::Merge => write!(f, "Merge exercises"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TransferError {
+    Io(io::Error),
+    Csv(csv::Error),
+    Json(serde_json::Error),
+    Empty,
+}
+
+impl Display for TransferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::Io(error) => write!(f, "{}", error),
+            TransferError::Csv(error) => write!(f, "malformed csv: {}", error),
+            TransferError::Json(error) => write!(f, "malformed json: {}", error),
+            TransferError::Empty => write!(f, "the file contains no workouts"),
+        }
+    }
+}
+
+impl std::error::Error for TransferError {}
+
+impl From<io::Error> for TransferError {
+    fn from(error: io::Error) -> Self {
+        TransferError::Io(error)
+    }
+}
+
+impl From<csv::Error> for TransferError {
+    fn from(error: csv::Error) -> Self {
+        TransferError::Csv(error)
+    }
+}
+
+impl From<serde_json::Error> for TransferError {
+    fn from(error: serde_json::Error) -> Self {
+        TransferError::Json(error)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonDocument {
+    #[serde(default)]
+    index: Option<usize>,
+    workouts: Vec<Workout>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRecord {
+    text: String,
+    #[serde(default)]
+    exercises: String,
+    #[serde(default, deserialize_with = "csv::invalid_option")]
+    current: Option<bool>,
+}
+
+/// The workouts read from a file, checked against the rotation they are imported into.
+#[derive(Debug, Clone)]
+pub struct ImportPreview {
+    pub workouts: Vec<Workout>,
+    pub index: Option<usize>,
+    /// Texts that are already part of the rotation.
+    pub duplicates: Vec<String>,
+    /// Texts that occur more than once in the file; only the first occurrence is kept unless
+    /// the rotation allows duplicates.
+    pub repeated: Vec<String>,
+}
+
+impl ImportPreview {
+    pub fn new(
+        imported: Vec<Workout>,
+        index: Option<usize>,
+        existing: &[Workout],
+        allow_duplicates: bool,
+    ) -> ImportPreview {
+        let mut workouts: Vec<Workout> = vec![];
+        let mut repeated = vec![];
+        let mut current = None;
+        for (i, workout) in imported.into_iter().enumerate() {
+            let position = if is_valid_workout_text(&workout.text, &workouts, allow_duplicates) {
+                workouts.push(workout);
+                Some(workouts.len() - 1)
+            } else if !workout.text.is_empty() {
+                let first = workouts.iter().position(|w| w.text == workout.text);
+                repeated.push(workout.text);
+                first
+            } else {
+                None
+            };
+            if index == Some(i) {
+                current = position;
+            }
+        }
+        let duplicates = workouts
+            .iter()
+            .filter(|w| !is_valid_workout_text(&w.text, existing, false))
+            .map(|w| w.text.clone())
+            .collect();
+        let index = current;
+
+        ImportPreview {
+            workouts,
+            index,
+            duplicates,
+            repeated,
+        }
+    }
+
+    pub fn new_count(&self) -> usize {
+        self.workouts.len() - self.duplicates.len()
+    }
+}
+
+pub fn export_workouts(
+    path: &Path,
+    format: TransferFormat,
+    workouts: &[Workout],
+    index: usize,
+) -> Result<(), TransferError> {
+    write_atomically(path, format_workouts(format, workouts, index)?.as_bytes())?;
+
+    Ok(())
+}
+
+pub fn import_workouts(
+    path: &Path,
+    format: TransferFormat,
+    existing: &[Workout],
+    allow_duplicates: bool,
+) -> Result<ImportPreview, TransferError> {
+    let content = fs::read_to_string(path)?;
+    let (workouts, index) = parse_workouts(format, &content)?;
+    if workouts.is_empty() {
+        return Err(TransferError::Empty);
+    }
+
+    Ok(ImportPreview::new(
+        workouts,
+        index,
+        existing,
+        allow_duplicates,
+    ))
+}
+
+pub fn apply_import(
+    workouts: &mut Vec<Workout>,
+    index: &mut usize,
+    preview: ImportPreview,
+    mode: MergeMode,
+) {
+    if mode == MergeMode::Replace {
+        let count = preview.workouts.len();
+        *workouts = preview.workouts;
+        *index = preview.index.filter(|i| *i < count).unwrap_or(0);
+        return;
+    }
+
+    // only entries of the rotation count as duplicates, repeats kept by the preview are appended
+    let existing_count = workouts.len();
+    for mut workout in preview.workouts {
+        if let Some(existing) = workouts[..existing_count]
+            .iter_mut()
+            .find(|w| w.text == workout.text)
+        {
+            if mode == MergeMode::Merge && !workout.exercises.is_empty() {
+                existing.exercises = workout.exercises;
+            }
+            continue;
+        }
+
+        if workouts.iter().any(|w| w.id == workout.id) {
+            workout.id = Uuid::new_v4();
+        }
+        workouts.push(workout);
+    }
+}
+
+fn format_workouts(
+    format: TransferFormat,
+    workouts: &[Workout],
+    index: usize,
+) -> Result<String, TransferError> {
+    let content = match format {
+        TransferFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            for (i, workout) in workouts.iter().enumerate() {
+                writer.serialize(CsvRecord {
+                    text: workout.text.clone(),
+                    exercises: format_exercises(&workout.exercises),
+                    current: Some(i == index),
+                })?;
+            }
+            let buffer = writer.into_inner().map_err(|e| e.into_error())?;
+            String::from_utf8_lossy(&buffer).into_owned()
+        }
+        TransferFormat::Json => serde_json::to_string_pretty(&JsonDocument {
+            index: Some(index),
+            workouts: workouts.to_vec(),
+        })?,
+        TransferFormat::Text => workouts.iter().map(|w| format!("{}\n", w.text)).collect(),
+    };
+
+    Ok(content)
+}
+
+fn parse_workouts(
+    format: TransferFormat,
+    content: &str,
+) -> Result<(Vec<Workout>, Option<usize>), TransferError> {
+    let parsed = match format {
+        TransferFormat::Csv => {
+            let mut reader = csv::Reader::from_reader(content.as_bytes());
+            let mut workouts = vec![];
+            let mut index = None;
+            for record in reader.deserialize::<CsvRecord>() {
+                let record = record?;
+                if record.current == Some(true) {
+                    index = Some(workouts.len());
+                }
+                workouts.push(Workout {
+                    exercises: parse_exercises(&record.exercises),
+                    ..Workout::new(record.text.trim().to_owned())
+                });
+            }
+            (workouts, index)
+        }
+        TransferFormat::Json => {
+            let document: JsonDocument = serde_json::from_str(content)?;
+            (document.workouts, document.index)
+        }
+        TransferFormat::Text => {
+            let workouts = content
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(|l| Workout::new(l.to_owned()))
+                .collect();
+            (workouts, None)
+        }
+    };
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transfer::{
+        ImportPreview, MergeMode, TransferFormat, apply_import, format_workouts, parse_workouts,
+    };
+    use crate::workout::{Program, Workout, parse_exercises};
+
+    fn create_program(index: usize, texts: &[&str]) -> Program {
+        Program {
+            index,
+            workouts: texts.iter().map(|t| Workout::new(t.to_string())).collect(),
+            ..Program::default()
+        }
+    }
+
+    fn get_texts(workouts: &[Workout]) -> Vec<&str> {
+        workouts.iter().map(|w| w.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_format_workouts_given_csv_should_round_trip() {
+        let mut program = create_program(1, &["Push, heavy", "Pull"]);
+        program.workouts[0].exercises = parse_exercises("Bench 5x5 @80kg\nDips 3x10");
+
+        let content =
+            format_workouts(TransferFormat::Csv, &program.workouts, program.index).unwrap();
+        let (workouts, index) = parse_workouts(TransferFormat::Csv, &content).unwrap();
+
+        assert_eq!(vec!["Push, heavy", "Pull"], get_texts(&workouts));
+        assert_eq!(program.workouts[0].exercises, workouts[0].exercises);
+        assert_eq!(Some(1), index)
+    }
+
+    #[test]
+    fn test_format_workouts_given_json_should_keep_ids() {
+        let program = create_program(0, &["workout1"]);
+
+        let content =
+            format_workouts(TransferFormat::Json, &program.workouts, program.index).unwrap();
+        let (workouts, _) = parse_workouts(TransferFormat::Json, &content).unwrap();
+
+        assert_eq!(program.workouts[0].id, workouts[0].id)
+    }
+
+    #[test]
+    fn test_parse_workouts_given_text_should_skip_blank_lines() {
+        let (workouts, index) =
+            parse_workouts(TransferFormat::Text, "workout1\n\n  workout2  \n").unwrap();
+
+        assert_eq!(vec!["workout1", "workout2"], get_texts(&workouts));
+        assert_eq!(None, index)
+    }
+
+    #[test]
+    fn test_parse_workouts_given_csv_without_current_column_should_parse() {
+        let (workouts, index) = parse_workouts(TransferFormat::Csv, "text\nworkout1\n").unwrap();
+
+        assert_eq!(vec!["workout1"], get_texts(&workouts));
+        assert_eq!(None, index)
+    }
+
+    #[test]
+    fn test_import_preview_given_existing_and_repeated_texts_should_report_them() {
+        let existing = create_program(0, &["workout1"]).workouts;
+        let imported = create_program(0, &["workout1", "workout2", "workout2"]).workouts;
+
+        let preview = ImportPreview::new(imported, None, &existing, false);
+
+        assert_eq!(vec!["workout1", "workout2"], get_texts(&preview.workouts));
+        assert_eq!(vec!["workout1"], preview.duplicates);
+        assert_eq!(vec!["workout2"], preview.repeated);
+        assert_eq!(1, preview.new_count())
+    }
+
+    #[test]
+    fn test_import_preview_given_duplicates_allowed_should_keep_repeated_texts() {
+        let imported = create_program(0, &["A", "B", "A", "C"]).workouts;
+
+        let preview = ImportPreview::new(imported, Some(2), &[], true);
+
+        assert_eq!(vec!["A", "B", "A", "C"], get_texts(&preview.workouts));
+        assert!(preview.repeated.is_empty());
+        assert_eq!(Some(2), preview.index)
+    }
+
+    #[test]
+    fn test_apply_import_given_append_should_skip_duplicates() {
+        let mut program = create_program(1, &["workout1", "workout2"]);
+        let imported = create_program(0, &["workout2", "workout3"]).workouts;
+        let preview = ImportPreview::new(imported, Some(0), &program.workouts, false);
+
+        apply_import(
+            &mut program.workouts,
+            &mut program.index,
+            preview,
+            MergeMode::Append,
+        );
+
+        assert_eq!(
+            vec!["workout1", "workout2", "workout3"],
+            get_texts(&program.workouts)
+        );
+        assert_eq!(1, program.index)
+    }
+
+    #[test]
+    fn test_apply_import_given_append_with_duplicates_allowed_should_keep_repeats() {
+        let mut program = create_program(0, &["B"]);
+        let imported = create_program(0, &["A", "B", "A", "C"]).workouts;
+        let preview = ImportPreview::new(imported, None, &program.workouts, true);
+        let new_count = preview.new_count();
+
+        apply_import(
+            &mut program.workouts,
+            &mut program.index,
+            preview,
+            MergeMode::Append,
+        );
+
+        assert_eq!(vec!["B", "A", "A", "C"], get_texts(&program.workouts));
+        assert_eq!(3, new_count)
+    }
+
+    #[test]
+    fn test_apply_import_given_merge_should_take_imported_exercises() {
+        let mut program = create_program(0, &["workout1"]);
+        let mut imported = create_program(0, &["workout1"]).workouts;
+        imported[0].exercises = parse_exercises("Squat 5x5");
+        let preview = ImportPreview::new(imported, None, &program.workouts, false);
+
+        apply_import(
+            &mut program.workouts,
+            &mut program.index,
+            preview,
+            MergeMode::Merge,
+        );
+
+        assert_eq!(1, program.workouts.len());
+        assert_eq!(1, program.workouts[0].exercises.len())
+    }
+
+    #[test]
+    fn test_apply_import_given_replace_should_take_imported_index() {
+        let mut program = create_program(0, &["workout1"]);
+        let imported = create_program(0, &["workout2", "workout3"]).workouts;
+        let preview = ImportPreview::new(imported, Some(1), &program.workouts, false);
+
+        apply_import(
+            &mut program.workouts,
+            &mut program.index,
+            preview,
+            MergeMode::Replace,
+        );
+
+        assert_eq!(vec!["workout2", "workout3"], get_texts(&program.workouts));
+        assert_eq!(1, program.index)
+    }
+
+    #[test]
+    fn test_apply_import_given_colliding_id_should_assign_new_id() {
+        let mut program = create_program(0, &["workout1"]);
+        let imported = vec![Workout {
+            text: String::from("workout2"),
+            ..program.workouts[0].clone()
+        }];
+        let preview = ImportPreview::new(imported, None, &program.workouts, false);
+
+        apply_import(
+            &mut program.workouts,
+            &mut program.index,
+            preview,
+            MergeMode::Append,
+        );
+
+        assert_ne!(program.workouts[0].id, program.workouts[1].id)
+    }
+}
@@ -1,11 +1,31 @@
+pub mod chart;
+pub mod completion_dialog;
 pub mod confirmation_dialog;
+pub mod exercise_editor_page;
+pub mod help_overlay;
+pub mod history_page;
+pub mod import_dialog;
+pub mod recovery_page;
+pub mod schedule_page;
+pub mod session_page;
 pub mod settings_page;
+pub mod stats_page;
+pub mod timer_panel;
+pub mod transfer_page;
 
 use crate::Message;
 use crate::helper::ContainerExtensions;
+use crate::ui::timer_panel::{TimerViewModel, create_timer_panel};
+use crate::workout::LabelFilter;
+use iced::alignment::{Horizontal, Vertical};
 use iced::widget::tooltip::Position;
-use iced::widget::{Column, Container, Row, Tooltip, button, center, horizontal_space, text};
-use iced::{Element, Padding};
+use iced::widget::{
+    Column, Container, Row, Scrollable, Tooltip, button, center, horizontal_space, pick_list, text,
+};
+use iced::{Color, Element, Length, Padding};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
 
 pub const WINDOW_WIDTH: f32 = 500.0;
 pub const WINDOW_HEIGHT: f32 = 300.0;
@@ -16,60 +36,270 @@ const SPACING_M: f32 = 10.0;
 const SPACING_X: f32 = 15.0;
 const SPACING_XL: f32 = 20.0;
 const SPACING_XXL: f32 = 30.0;
+const LABEL_COLOR: Color = Color::from_rgb(0.6, 0.7, 0.9);
 
 pub enum Page {
     Main,
     Settings,
+    Recovery,
+    ExerciseEditor,
+    History,
+    Transfer,
+    Schedule,
+    Session,
+    Stats,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramOption {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl Display for ProgramOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelFilterOption {
+    pub filter: Option<LabelFilter>,
+}
+
+impl Display for LabelFilterOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.filter {
+            None => write!(f, "All workouts"),
+            Some(filter) => write!(f, "{}", filter),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkoutOption {
+    pub position: usize,
+    pub text: String,
+}
+
+impl Display for WorkoutOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. {}", self.position + 1, self.text)
+    }
 }
 
 pub struct MainViewModel {
+    pub programs: Vec<ProgramOption>,
+    pub program: Option<ProgramOption>,
     pub workout: String,
+    pub exercises: Vec<ExerciseLine>,
     pub has_next: bool,
-    pub selected_number: i8,
+    pub has_current: bool,
+    pub has_exercises: bool,
+    pub selected_number: usize,
     pub total: usize,
+    pub workout_options: Vec<WorkoutOption>,
+    pub filter_options: Vec<LabelFilterOption>,
+    /// Limits which workouts advancing lands on.
+    pub filter: LabelFilterOption,
+    /// Category and tags of the current workout.
+    pub labels: Option<String>,
+    pub notes: Option<String>,
+    pub schedule_note: Option<String>,
+    pub timer: Option<TimerViewModel>,
+}
+
+pub struct ExerciseLine {
+    pub name: String,
+    pub targets: String,
+    /// Targets for this session computed from the progression of the exercise.
+    pub suggestion: Option<String>,
 }
 
 pub fn create_main_page<'a>(view_model: MainViewModel) -> impl Into<Element<'a, Message>> {
     Column::new()
-        .push(create_header())
-        .push(create_body(view_model.workout, view_model.has_next))
-        .push(create_footer(view_model.selected_number, view_model.total))
+        .push(create_header(
+            view_model.programs,
+            view_model.program,
+            view_model.filter_options,
+            view_model.filter,
+        ))
+        .push(create_body(
+            view_model.workout,
+            (view_model.labels, view_model.notes),
+            view_model.exercises,
+            view_model.has_next,
+            view_model.has_current,
+            view_model.has_exercises,
+            view_model.schedule_note,
+        ))
+        .push_maybe(view_model.timer.map(create_timer_panel))
+        .push(create_footer(
+            view_model.selected_number,
+            view_model.total,
+            view_model.workout_options,
+            view_model.has_next,
+        ))
 }
 
-fn create_header<'a>() -> impl Into<Element<'a, Message>> {
+fn create_header<'a>(
+    programs: Vec<ProgramOption>,
+    program: Option<ProgramOption>,
+    filter_options: Vec<LabelFilterOption>,
+    filter: LabelFilterOption,
+) -> impl Into<Element<'a, Message>> {
+    let program_pick = pick_list(programs, program, Message::SwitchProgram).text_size(14);
+    // only offered once there is something to filter by, or to clear a stale filter
+    let filter_pick = (filter_options.len() > 1 || filter.filter.is_some()).then(|| {
+        pick_list(
+            filter_options,
+            Some(filter),
+            Message::RotationFilterSelection,
+        )
+        .text_size(14)
+    });
+    let history_btn = button("H").on_press(Message::OpenHistory);
+    let history_tooltip = Tooltip::new(history_btn, text("History").size(14.0), Position::Left);
     let settings_btn = button("S").on_press(Message::OpenSettings);
     let settings_tooltip = Tooltip::new(settings_btn, text("Settings").size(14.0), Position::Left);
-    let row = Row::new().push(horizontal_space()).push(settings_tooltip);
+    let timer_btn = button("T").on_press(Message::ToggleTimerPanel);
+    let timer_tooltip = Tooltip::new(timer_btn, text("Timer").size(14.0), Position::Left);
+    let help_btn = button("?").on_press(Message::ToggleHelp);
+    let help_tooltip = Tooltip::new(help_btn, text("Shortcuts").size(14.0), Position::Left);
+    let row = Row::new()
+        .push(program_pick)
+        .push_maybe(filter_pick)
+        .push(horizontal_space())
+        .push(help_tooltip)
+        .push(timer_tooltip)
+        .push(history_tooltip)
+        .push(settings_tooltip)
+        .spacing(SPACING_S);
 
     center(row)
         .height(HEADER_HEIGHT)
-        .padding(Padding::ZERO.right(SPACING_M))
+        .padding(Padding::ZERO.left(SPACING_M).right(SPACING_M))
         .dev_background()
 }
 
-fn create_body<'a>(workout: String, has_next: bool) -> impl Into<Element<'a, Message>> {
-    let text = center(text(workout).size(28));
-    let button = center(
-        button("Next")
-            .on_press_maybe(if has_next {
-                Some(Message::NextWorkout)
-            } else {
-                None
-            })
-            .padding(Padding::from([SPACING_X, SPACING_XXL])),
+fn create_body<'a>(
+    workout: String,
+    (labels, notes): (Option<String>, Option<String>),
+    exercises: Vec<ExerciseLine>,
+    has_next: bool,
+    has_current: bool,
+    has_exercises: bool,
+    schedule_note: Option<String>,
+) -> impl Into<Element<'a, Message>> {
+    let note = schedule_note.map(|n| center(text(n).size(14)).height(Length::Shrink));
+    let content: Element<'a, Message> = if exercises.is_empty() {
+        center(
+            Column::new()
+                .push(text(workout).size(28))
+                .push_maybe(labels.map(|l| text(l).size(14).color(LABEL_COLOR)))
+                .push_maybe(notes.map(|n| text(n).size(14)))
+                .spacing(SPACING_S)
+                .align_x(Horizontal::Center),
+        )
+        .into()
+    } else {
+        create_workout_card(workout, labels, notes, exercises).into()
+    };
+    let complete_btn = button("Complete")
+        .on_press_maybe(has_current.then_some(Message::InitiateCompletion))
+        .padding(Padding::from([SPACING_X, SPACING_XXL]));
+    let skip_btn = button("Skip")
+        .on_press_maybe(if has_next {
+            Some(Message::InitiateSkip)
+        } else {
+            None
+        })
+        .padding(Padding::from([SPACING_X, SPACING_XXL]));
+    let session_btn = button("Session")
+        .on_press_maybe(has_exercises.then_some(Message::StartSession))
+        .padding(Padding::from([SPACING_X, SPACING_XXL]));
+    let buttons = center(
+        Row::new()
+            .push(complete_btn)
+            .push(session_btn)
+            .push(skip_btn)
+            .spacing(SPACING_XL),
     );
 
-    Container::new(Column::new().push(text).push(button))
+    Container::new(Column::new().push_maybe(note).push(content).push(buttons))
         .padding(Padding::ZERO.top(SPACING_XL).bottom(SPACING_XL))
         .dev_background()
 }
 
-fn create_footer<'a>(number: i8, total: usize) -> impl Into<Element<'a, Message>> {
-    let text = text(format!("{} from {}", number, total));
-    let row = Row::new().push(text).push(horizontal_space());
+fn create_workout_card<'a>(
+    workout: String,
+    labels: Option<String>,
+    notes: Option<String>,
+    exercises: Vec<ExerciseLine>,
+) -> impl Into<Element<'a, Message>> {
+    let exercises_column = exercises
+        .into_iter()
+        .fold(Column::new(), |column: Column<'a, Message>, line| {
+            column.push(
+                Row::new()
+                    .push(text(line.name).size(16))
+                    .push(horizontal_space())
+                    .push(text(line.targets).size(16))
+                    .push_maybe(line.suggestion.map(|s| {
+                        text(format!("\u{2192} {}", s))
+                            .size(16)
+                            .color(Color::from_rgb8(90, 200, 120))
+                    }))
+                    .spacing(SPACING_S),
+            )
+        })
+        .spacing(SPACING_S)
+        .padding(Padding::ZERO.right(SPACING_X));
+    let card = Column::new()
+        .push(
+            Row::new()
+                .push(text(workout).size(22))
+                .push_maybe(labels.map(|l| text(l).size(14).color(LABEL_COLOR)))
+                .spacing(SPACING_M)
+                .align_y(Vertical::Center),
+        )
+        .push_maybe(notes.map(|n| text(n).size(14)))
+        .push(Scrollable::new(exercises_column).height(Length::Fill))
+        .spacing(SPACING_M)
+        .padding(Padding::from([0.0, SPACING_XXL]));
+
+    Container::new(card)
+        .height(Length::Fill)
+        .width(Length::Fill)
+}
+
+fn create_footer<'a>(
+    number: usize,
+    total: usize,
+    workout_options: Vec<WorkoutOption>,
+    has_previous: bool,
+) -> impl Into<Element<'a, Message>> {
+    let previous_btn = button(text("\u{2190}").size(14))
+        .on_press_maybe(has_previous.then_some(Message::PreviousWorkout));
+    let previous_tooltip = Tooltip::new(previous_btn, text("Previous").size(14.0), Position::Right);
+    let number_txt = text(format!("{} from {}", number, total));
+    let selected = workout_options
+        .iter()
+        .find(|o| number.checked_sub(1) == Some(o.position))
+        .cloned();
+    let jump_pick = pick_list(workout_options, selected, Message::JumpToWorkout)
+        .placeholder("Jump to")
+        .text_size(14);
+    let row = Row::new()
+        .push(previous_tooltip)
+        .push(number_txt)
+        .push(horizontal_space())
+        .push(jump_pick)
+        .spacing(SPACING_M)
+        .align_y(Vertical::Center);
 
     center(row)
         .height(FOOTER_HEIGHT)
-        .padding(Padding::ZERO.left(SPACING_M))
+        .padding(Padding::ZERO.left(SPACING_M).right(SPACING_M))
         .dev_background()
 }
@@ -0,0 +1,159 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::canvas::{Frame, Geometry, Path, Program, Stroke, Text};
+use iced::{Color, Pixels, Point, Rectangle, Renderer, Size, Theme, mouse};
+
+const LABEL_HEIGHT: f32 = 16.0;
+const VALUE_HEIGHT: f32 = 14.0;
+const BAR_GAP: f32 = 4.0;
+const DOT_RADIUS: f32 = 3.0;
+/// Labels beyond this count are thinned out so that they do not overlap.
+const MAX_LABELS: usize = 8;
+const TEXT_COLOR: Color = Color::from_rgb(0.8, 0.8, 0.8);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartKind {
+    Bar,
+    Line,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPoint {
+    pub label: String,
+    pub value: f32,
+}
+
+/// Draws values from left to right, scaled to the largest one.
+#[derive(Debug, Clone)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub points: Vec<ChartPoint>,
+    pub color: Color,
+}
+
+impl<Message> Program<Message> for Chart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _: &(),
+        renderer: &Renderer,
+        _: &Theme,
+        bounds: Rectangle,
+        _: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        if self.points.is_empty() {
+            frame.fill_text(create_text(
+                String::from("No data yet"),
+                frame.center(),
+                Vertical::Center,
+            ));
+            return vec![frame.into_geometry()];
+        }
+
+        let plot_height = (bounds.height - LABEL_HEIGHT - VALUE_HEIGHT).max(0.0);
+        let slot_width = bounds.width / self.points.len() as f32;
+        let max = self
+            .points
+            .iter()
+            .map(|p| p.value)
+            .fold(0.0_f32, f32::max)
+            .max(f32::EPSILON);
+        let label_step = self.points.len().div_ceil(MAX_LABELS);
+        let get_top = |value: f32| VALUE_HEIGHT + plot_height * (1.0 - value / max);
+
+        let centers: Vec<Point> = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Point::new(slot_width * (i as f32 + 0.5), get_top(p.value)))
+            .collect();
+
+        match self.kind {
+            ChartKind::Bar => {
+                for center in &centers {
+                    let width = (slot_width - BAR_GAP).max(1.0);
+                    frame.fill_rectangle(
+                        Point::new(center.x - width / 2.0, center.y),
+                        Size::new(width, VALUE_HEIGHT + plot_height - center.y),
+                        self.color,
+                    );
+                }
+            }
+            ChartKind::Line => {
+                let line = Path::new(|builder| {
+                    builder.move_to(centers[0]);
+                    for center in &centers[1..] {
+                        builder.line_to(*center);
+                    }
+                });
+                frame.stroke(
+                    &line,
+                    Stroke::default().with_color(self.color).with_width(2.0),
+                );
+                for center in &centers {
+                    frame.fill(&Path::circle(*center, DOT_RADIUS), self.color);
+                }
+            }
+        }
+
+        for (i, (point, center)) in self.points.iter().zip(&centers).enumerate() {
+            if i % label_step != 0 && i != self.points.len() - 1 {
+                continue;
+            }
+
+            frame.fill_text(create_text(
+                format_value(point.value),
+                Point::new(center.x, center.y),
+                Vertical::Bottom,
+            ));
+            frame.fill_text(create_text(
+                point.label.clone(),
+                Point::new(center.x, bounds.height),
+                Vertical::Bottom,
+            ));
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+fn create_text(content: String, position: Point, vertical: Vertical) -> Text {
+    Text {
+        content,
+        position,
+        color: TEXT_COLOR,
+        size: Pixels(11.0),
+        horizontal_alignment: Horizontal::Center,
+        vertical_alignment: vertical,
+        ..Text::default()
+    }
+}
+
+/// Shows whole numbers without decimals and larger values in thousands, e.g. `12.5k`.
+pub fn format_value(value: f32) -> String {
+    if value >= 10_000.0 {
+        format!("{:.0}k", value / 1000.0)
+    } else if value >= 1000.0 {
+        format!("{:.1}k", value / 1000.0)
+    } else if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::chart::format_value;
+
+    #[test]
+    fn test_format_value_given_thousands_should_abbreviate() {
+        assert_eq!("2.5k", format_value(2480.0))
+    }
+
+    #[test]
+    fn test_format_value_given_whole_number_should_drop_decimals() {
+        assert_eq!("42", format_value(42.0))
+    }
+}
@@ -0,0 +1,135 @@
+use crate::Message;
+use crate::history::Outcome;
+use crate::ui::{SPACING_M, SPACING_S, SPACING_XL};
+use iced::alignment::Horizontal;
+use iced::widget::container::Style;
+use iced::widget::{Column, Row, Space, button, center, text, text_input};
+use iced::{Border, Color, Element};
+use uuid::Uuid;
+
+const DIALOG_WIDTH: f32 = 300.0;
+const DIALOG_HEIGHT: f32 = 200.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct CompletionInput {
+    /// The workout the dialog was opened for, which stays the same if the rotation moves.
+    pub workout_id: Uuid,
+    pub outcome: Outcome,
+    pub note: String,
+    pub duration: String,
+}
+
+impl CompletionInput {
+    pub fn new(workout_id: Uuid, outcome: Outcome) -> CompletionInput {
+        CompletionInput {
+            workout_id,
+            outcome,
+            ..CompletionInput::default()
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.duration.trim().is_empty() || self.duration_secs().is_some()
+    }
+
+    /// The duration is entered in whole minutes.
+    pub fn duration_secs(&self) -> Option<u32> {
+        self.duration
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .and_then(|m| m.checked_mul(60))
+    }
+
+    pub fn note(&self) -> Option<String> {
+        Some(self.note.trim().to_owned()).filter(|n| !n.is_empty())
+    }
+}
+
+pub fn create_completion_dialog<'a>(
+    workout: String,
+    input: CompletionInput,
+) -> impl Into<Element<'a, Message>> {
+    let (title, note_placeholder) = match input.outcome {
+        Outcome::Completed => (format!("Completed '{}'", workout), "Note (optional)"),
+        Outcome::Skipped => (format!("Skipping '{}'", workout), "Reason (optional)"),
+    };
+    let title_txt = text(title);
+    let note_input =
+        text_input(note_placeholder, &input.note).on_input(Message::CompletionNoteInput);
+    let duration_input = (input.outcome == Outcome::Completed).then(|| {
+        text_input("Duration in minutes (optional)", &input.duration)
+            .on_input(Message::CompletionDurationInput)
+    });
+    let ok_btn = button(text("Ok")).on_press_maybe(
+        input
+            .is_valid()
+            .then_some(Message::CloseCompletionDialog(true)),
+    );
+    let cancel_btn = button(text("Cancel")).on_press(Message::CloseCompletionDialog(false));
+
+    let button_row = Row::new().push(ok_btn).push(cancel_btn).spacing(SPACING_XL);
+
+    center(
+        Column::new()
+            .push(title_txt)
+            .push(note_input)
+            .push_maybe(duration_input)
+            .push(Space::with_height(SPACING_M))
+            .push(button_row)
+            .spacing(SPACING_S)
+            .align_x(Horizontal::Center),
+    )
+    .width(DIALOG_WIDTH)
+    .height(DIALOG_HEIGHT)
+    .padding(SPACING_M)
+    .style(|_| Style {
+        background: Some(Color::BLACK.into()),
+        border: Border {
+            color: Color::from_rgb8(130, 130, 130),
+            width: 2.0,
+            radius: 5.0.into(),
+        },
+        ..Style::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::completion_dialog::CompletionInput;
+
+    #[test]
+    fn test_is_valid_given_empty_duration_should_be_true() {
+        assert!(CompletionInput::default().is_valid())
+    }
+
+    #[test]
+    fn test_is_valid_given_non_numeric_duration_should_be_false() {
+        let input = CompletionInput {
+            duration: String::from("half an hour"),
+            ..CompletionInput::default()
+        };
+
+        assert!(!input.is_valid())
+    }
+
+    #[test]
+    fn test_duration_secs_given_minutes_should_convert() {
+        let input = CompletionInput {
+            duration: String::from(" 45 "),
+            ..CompletionInput::default()
+        };
+
+        assert_eq!(Some(2700), input.duration_secs())
+    }
+
+    #[test]
+    fn test_note_given_blank_note_should_return_none() {
+        let input = CompletionInput {
+            note: String::from("  "),
+            ..CompletionInput::default()
+        };
+
+        assert_eq!(None, input.note())
+    }
+}
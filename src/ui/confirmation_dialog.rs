@@ -10,8 +10,8 @@ const DIALOG_HEIGHT: f32 = 130.0;
 
 #[derive(Debug, Clone)]
 pub enum ConfirmationTopic {
-    WorkoutDeletion,
-    Clearance,
+    ProgramDeletion,
+    ExportOverwrite,
 }
 
 #[derive(Debug, Clone)]
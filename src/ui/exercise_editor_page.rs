@@ -0,0 +1,104 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::ui::{SPACING_M, SPACING_S, WINDOW_HEIGHT};
+use iced::alignment::Vertical;
+use iced::widget::{Column, Row, button, center, horizontal_space, text, text_editor, text_input};
+use iced::{Element, Length, Padding};
+
+const FOOTER_HEIGHT: f32 = 50.0;
+const PICK_WEIGHT_INPUT_WIDTH: f32 = 50.0;
+const CATEGORY_INPUT_WIDTH: f32 = 120.0;
+const HINT: &str = "One exercise per line, e.g. 'Squat 5x5 @100kg' or 'Plank 3x 45s'";
+
+pub struct ExerciseEditorViewModel<'a> {
+    pub workout: String,
+    pub content: &'a text_editor::Content,
+    pub notes: &'a text_editor::Content,
+    pub category: String,
+    /// Comma separated, e.g. `upper, push`.
+    pub tags: String,
+    pub pick_weight: String,
+    pub is_pick_weight_valid: bool,
+}
+
+pub fn create_exercise_editor_page<'a>(
+    view_model: ExerciseEditorViewModel<'a>,
+) -> impl Into<Element<'a, Message>> {
+    Column::new()
+        .push(create_body(
+            view_model.workout,
+            view_model.content,
+            view_model.notes,
+            (view_model.category, view_model.tags),
+            view_model.pick_weight,
+        ))
+        .push(create_footer(view_model.is_pick_weight_valid))
+}
+
+fn create_body<'a>(
+    workout: String,
+    content: &'a text_editor::Content,
+    notes: &'a text_editor::Content,
+    (category, tags): (String, String),
+    pick_weight: String,
+) -> impl Into<Element<'a, Message>> {
+    let editor = text_editor(content)
+        .placeholder("Squat 5x5 @100kg")
+        .on_action(Message::ExerciseEditorAction)
+        .height(Length::FillPortion(3));
+    let notes_editor = text_editor(notes)
+        .placeholder("Notes")
+        .on_action(Message::NotesEditorAction)
+        .size(14)
+        .height(Length::FillPortion(2));
+    let labels_row = Row::new()
+        .push(
+            text_input("Category", &category)
+                .on_input(Message::CategoryInput)
+                .size(14)
+                .width(CATEGORY_INPUT_WIDTH),
+        )
+        .push(
+            text_input("Tags, e.g. upper, cardio", &tags)
+                .on_input(Message::TagsInput)
+                .size(14),
+        )
+        .spacing(SPACING_S);
+    let pick_weight_input = text_input("1", &pick_weight)
+        .on_input(Message::PickWeightInput)
+        .size(14)
+        .width(PICK_WEIGHT_INPUT_WIDTH);
+    let title_row = Row::new()
+        .push(text(workout).size(18))
+        .push(horizontal_space())
+        .push(text("Frequency").size(14))
+        .push(pick_weight_input)
+        .spacing(SPACING_S)
+        .align_y(Vertical::Center);
+
+    Column::new()
+        .push(title_row)
+        .push(text(HINT).size(12))
+        .push(editor)
+        .push(labels_row)
+        .push(notes_editor)
+        .spacing(SPACING_S)
+        .padding(SPACING_M)
+        .height(WINDOW_HEIGHT - FOOTER_HEIGHT)
+}
+
+fn create_footer<'a>(is_pick_weight_valid: bool) -> impl Into<Element<'a, Message>> {
+    let save_btn =
+        button("Save").on_press_maybe(is_pick_weight_valid.then_some(Message::SaveExercises));
+    let cancel_btn = button("Cancel").on_press(Message::CloseExerciseEditor);
+    let row = Row::new()
+        .push(horizontal_space())
+        .push(save_btn)
+        .push(cancel_btn)
+        .spacing(SPACING_S);
+
+    center(row)
+        .height(FOOTER_HEIGHT)
+        .padding(Padding::ZERO.right(SPACING_M))
+        .dev_background()
+}
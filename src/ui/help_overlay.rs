@@ -0,0 +1,45 @@
+use crate::Message;
+use crate::shortcut::SHORTCUTS;
+use crate::ui::{SPACING_M, SPACING_S};
+use iced::widget::container::Style;
+use iced::widget::{Column, Row, Scrollable, center, horizontal_space, text};
+use iced::{Border, Color, Element, Padding};
+
+const DIALOG_WIDTH: f32 = 360.0;
+const DIALOG_HEIGHT: f32 = 270.0;
+const KEY_WIDTH: f32 = 150.0;
+
+pub fn create_help_overlay<'a>() -> impl Into<Element<'a, Message>> {
+    let rows = SHORTCUTS.iter().fold(
+        Column::new().spacing(2),
+        |column: Column<'a, Message>, (key, description)| {
+            column.push(
+                Row::new()
+                    .push(text(*key).size(12).width(KEY_WIDTH))
+                    .push(text(*description).size(12))
+                    .push(horizontal_space()),
+            )
+        },
+    );
+
+    center(
+        Column::new()
+            .push(text("Keyboard shortcuts").size(16))
+            .push(Scrollable::new(
+                rows.padding(Padding::ZERO.right(SPACING_M)),
+            ))
+            .spacing(SPACING_S),
+    )
+    .width(DIALOG_WIDTH)
+    .height(DIALOG_HEIGHT)
+    .padding(SPACING_M)
+    .style(|_| Style {
+        background: Some(Color::BLACK.into()),
+        border: Border {
+            color: Color::from_rgb8(130, 130, 130),
+            width: 2.0,
+            radius: 5.0.into(),
+        },
+        ..Style::default()
+    })
+}
@@ -0,0 +1,132 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::history::{HistoryEntry, Outcome, describe_sets};
+use crate::ui::{SPACING_M, SPACING_S, SPACING_X, WINDOW_HEIGHT};
+use crate::workout::format_duration;
+use iced::widget::scrollable::{Direction, Scrollbar};
+use iced::widget::{
+    Column, Container, Row, Scrollable, button, center, horizontal_space, pick_list, text,
+    text_input,
+};
+use iced::{Element, Length, Padding};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+const FILTER_HEIGHT: f32 = 40.0;
+const FOOTER_HEIGHT: f32 = 50.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkoutFilterOption {
+    pub id: Option<Uuid>,
+    pub text: String,
+}
+
+impl Display for WorkoutFilterOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+pub struct HistoryViewModel {
+    pub entries: Vec<HistoryEntry>,
+    pub workout_options: Vec<WorkoutFilterOption>,
+    pub workout_selection: Option<WorkoutFilterOption>,
+    pub from_input: String,
+    pub to_input: String,
+}
+
+pub fn create_history_page<'a>(view_model: HistoryViewModel) -> impl Into<Element<'a, Message>> {
+    Column::new()
+        .push(create_filter_row(
+            view_model.workout_options,
+            view_model.workout_selection,
+            view_model.from_input,
+            view_model.to_input,
+        ))
+        .push(create_entries_list(view_model.entries))
+        .push(create_footer())
+}
+
+fn create_filter_row<'a>(
+    workout_options: Vec<WorkoutFilterOption>,
+    workout_selection: Option<WorkoutFilterOption>,
+    from_input: String,
+    to_input: String,
+) -> impl Into<Element<'a, Message>> {
+    let workout_pick = pick_list(
+        workout_options,
+        workout_selection,
+        Message::HistoryWorkoutFilter,
+    )
+    .width(Length::Fill);
+    let from_input = text_input("From YYYY-MM-DD", &from_input)
+        .on_input(Message::HistoryFromInput)
+        .width(120);
+    let to_input = text_input("To YYYY-MM-DD", &to_input)
+        .on_input(Message::HistoryToInput)
+        .width(120);
+
+    Row::new()
+        .push(workout_pick)
+        .push(from_input)
+        .push(to_input)
+        .spacing(SPACING_S)
+        .padding(SPACING_S)
+        .height(FILTER_HEIGHT)
+}
+
+fn create_entries_list<'a>(entries: Vec<HistoryEntry>) -> impl Into<Element<'a, Message>> {
+    let column = entries
+        .into_iter()
+        .fold(
+            Column::new(),
+            |column: Column<'a, Message>, entry: HistoryEntry| {
+                let mut details = Row::new()
+                    .push(text(entry.completed_at.format("%Y-%m-%d %H:%M").to_string()).size(14))
+                    .push(text(entry.workout_text).size(14))
+                    .spacing(SPACING_M);
+                if entry.outcome == Outcome::Skipped {
+                    details = details.push(text("skipped").size(14));
+                }
+                if let Some(secs) = entry.duration_secs {
+                    details = details
+                        .push(horizontal_space())
+                        .push(text(format_duration(secs)).size(14));
+                }
+
+                let mut item = Column::new().push(details);
+                if !entry.sets.is_empty() {
+                    item = item.push(text(describe_sets(&entry.sets)).size(12));
+                }
+                if let Some(note) = entry.note {
+                    item = item.push(text(note).size(12));
+                }
+
+                column.push(item)
+            },
+        )
+        .spacing(SPACING_S)
+        .padding(Padding::ZERO.right(SPACING_X));
+    let scrollable = Scrollable::with_direction(column, Direction::Vertical(Scrollbar::default()));
+
+    Container::new(scrollable)
+        .width(Length::Fill)
+        .height(WINDOW_HEIGHT - FILTER_HEIGHT - FOOTER_HEIGHT)
+        .padding(Padding::from([0.0, SPACING_M]))
+        .background(20, 20, 20)
+}
+
+fn create_footer<'a>() -> impl Into<Element<'a, Message>> {
+    let stats_btn = button("Stats").on_press(Message::OpenStats);
+    let ok_btn = button("Ok").on_press(Message::CloseHistory);
+    let row = Row::new()
+        .push(stats_btn)
+        .push(horizontal_space())
+        .push(ok_btn);
+
+    center(row)
+        .height(FOOTER_HEIGHT)
+        .padding(Padding::ZERO.right(SPACING_M))
+        .dev_background()
+}
@@ -0,0 +1,96 @@
+use crate::Message;
+use crate::transfer::{ImportPreview, MergeMode};
+use crate::ui::{SPACING_M, SPACING_S, SPACING_XL};
+use iced::alignment::Horizontal;
+use iced::widget::container::Style;
+use iced::widget::{Column, Row, Space, button, center, pick_list, text};
+use iced::{Border, Color, Element};
+
+const DIALOG_WIDTH: f32 = 360.0;
+const DIALOG_HEIGHT: f32 = 220.0;
+const MAX_LISTED: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct ImportInput {
+    pub preview: ImportPreview,
+    pub mode: MergeMode,
+}
+
+impl ImportInput {
+    pub fn new(preview: ImportPreview) -> ImportInput {
+        ImportInput {
+            preview,
+            mode: MergeMode::default(),
+        }
+    }
+}
+
+pub fn create_import_dialog<'a>(input: ImportInput) -> impl Into<Element<'a, Message>> {
+    let preview = input.preview;
+    let summary = text(format!(
+        "{} workout(s) read, {} new",
+        preview.workouts.len(),
+        preview.new_count()
+    ));
+    let duplicates_txt = (!preview.duplicates.is_empty()).then(|| {
+        text(format!(
+            "Already in list: {}",
+            summarize(&preview.duplicates)
+        ))
+        .size(14)
+    });
+    let repeated_txt = (!preview.repeated.is_empty()).then(|| {
+        text(format!(
+            "Repeated in file: {}",
+            summarize(&preview.repeated)
+        ))
+        .size(14)
+    });
+    let mode_pick = pick_list(
+        MergeMode::ALL,
+        Some(input.mode),
+        Message::MergeModeSelection,
+    );
+    let ok_btn = button(text("Import")).on_press(Message::CloseImportDialog(true));
+    let cancel_btn = button(text("Cancel")).on_press(Message::CloseImportDialog(false));
+
+    let button_row = Row::new().push(ok_btn).push(cancel_btn).spacing(SPACING_XL);
+
+    center(
+        Column::new()
+            .push(summary)
+            .push_maybe(duplicates_txt)
+            .push_maybe(repeated_txt)
+            .push(mode_pick)
+            .push(Space::with_height(SPACING_M))
+            .push(button_row)
+            .spacing(SPACING_S)
+            .align_x(Horizontal::Center),
+    )
+    .width(DIALOG_WIDTH)
+    .height(DIALOG_HEIGHT)
+    .padding(SPACING_M)
+    .style(|_| Style {
+        background: Some(Color::BLACK.into()),
+        border: Border {
+            color: Color::from_rgb8(130, 130, 130),
+            width: 2.0,
+            radius: 5.0.into(),
+        },
+        ..Style::default()
+    })
+}
+
+fn summarize(texts: &[String]) -> String {
+    let listed = texts
+        .iter()
+        .take(MAX_LISTED)
+        .map(|t| format!("'{}'", t))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match texts.len() {
+        n if n > MAX_LISTED => format!("{} and {} more", listed, n - MAX_LISTED),
+        _ => listed,
+    }
+}
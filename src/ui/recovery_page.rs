@@ -0,0 +1,53 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::ui::{SPACING_M, SPACING_S, SPACING_XL};
+use iced::alignment::Horizontal;
+use iced::widget::{Column, Row, Scrollable, Space, button, center, text};
+use iced::{Element, Padding};
+use std::path::PathBuf;
+
+#[derive(Default)]
+pub struct RecoveryViewModel {
+    pub error: String,
+    pub backups: Vec<PathBuf>,
+    pub can_reset_index: bool,
+}
+
+pub fn create_recovery_page<'a>(view_model: RecoveryViewModel) -> impl Into<Element<'a, Message>> {
+    let title_txt = text("The workouts file could not be loaded").size(18);
+    let error_txt = text(view_model.error).size(14);
+    let hint_txt = text("The damaged file is kept next to the original for inspection.").size(12);
+
+    let backups_column = view_model.backups.into_iter().fold(
+        Column::new().spacing(SPACING_S),
+        |column: Column<'a, Message>, backup: PathBuf| {
+            let name = backup
+                .file_name()
+                .map_or(String::new(), |n| n.to_string_lossy().into_owned());
+            column.push(
+                button(text(format!("Restore {}", name))).on_press(Message::RestoreBackup(backup)),
+            )
+        },
+    );
+    let reset_index_btn = button(text("Reset index"))
+        .on_press_maybe(view_model.can_reset_index.then_some(Message::ResetIndex));
+    let start_fresh_btn = button(text("Start fresh")).on_press(Message::StartFresh);
+    let action_row = Row::new()
+        .push(reset_index_btn)
+        .push(start_fresh_btn)
+        .spacing(SPACING_S);
+
+    let column = Column::new()
+        .push(title_txt)
+        .push(error_txt)
+        .push(Space::with_height(SPACING_M))
+        .push(backups_column)
+        .push(action_row)
+        .push(Space::with_height(SPACING_M))
+        .push(hint_txt)
+        .spacing(SPACING_S)
+        .align_x(Horizontal::Center)
+        .padding(Padding::from([SPACING_XL, SPACING_M]));
+
+    center(Scrollable::new(column)).dev_background()
+}
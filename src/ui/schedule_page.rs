@@ -0,0 +1,279 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::history::parse_date;
+use crate::schedule::{CatchUpPolicy, Schedule, ScheduleSettings};
+use crate::ui::{SPACING_M, SPACING_S, WINDOW_HEIGHT};
+use chrono::{NaiveDate, Weekday};
+use iced::alignment::Vertical;
+use iced::widget::{
+    Column, Row, button, center, checkbox, horizontal_space, pick_list, text, text_input,
+};
+use iced::{Element, Padding};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+const FOOTER_HEIGHT: f32 = 50.0;
+const NUMBER_INPUT_WIDTH: f32 = 50.0;
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScheduleMode {
+    Off,
+    Weekdays,
+    Pattern,
+}
+
+impl ScheduleMode {
+    pub const ALL: [ScheduleMode; 3] = [
+        ScheduleMode::Off,
+        ScheduleMode::Weekdays,
+        ScheduleMode::Pattern,
+    ];
+}
+
+impl Display for ScheduleMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleMode::Off => write!(f, "No schedule"),
+            ScheduleMode::Weekdays => write!(f, "Weekdays"),
+            ScheduleMode::Pattern => write!(f, "Days on / off"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleInput {
+    pub mode: ScheduleMode,
+    pub days: Vec<Weekday>,
+    pub on: String,
+    pub off: String,
+    pub start: String,
+    pub policy: CatchUpPolicy,
+}
+
+impl ScheduleInput {
+    pub fn new(settings: &ScheduleSettings, today: NaiveDate) -> ScheduleInput {
+        let input = ScheduleInput {
+            mode: ScheduleMode::Off,
+            days: vec![Weekday::Mon, Weekday::Wed, Weekday::Fri],
+            on: String::from("1"),
+            off: String::from("1"),
+            start: today.to_string(),
+            policy: settings.policy,
+        };
+
+        match &settings.schedule {
+            Schedule::Off => input,
+            Schedule::Weekdays { days } => ScheduleInput {
+                mode: ScheduleMode::Weekdays,
+                days: days.clone(),
+                ..input
+            },
+            Schedule::Pattern { on, off, start } => ScheduleInput {
+                mode: ScheduleMode::Pattern,
+                on: on.to_string(),
+                off: off.to_string(),
+                start: start.to_string(),
+                ..input
+            },
+        }
+    }
+
+    pub fn toggle_day(&mut self, day: Weekday, is_checked: bool) {
+        self.days.retain(|d| *d != day);
+        if is_checked {
+            self.days.push(day);
+            self.days.sort_by_key(|d| d.num_days_from_monday());
+        }
+    }
+
+    /// Returns `None` while the inputs do not describe a schedule with training days.
+    pub fn to_schedule(&self) -> Option<Schedule> {
+        match self.mode {
+            ScheduleMode::Off => Some(Schedule::Off),
+            ScheduleMode::Weekdays => Some(Schedule::Weekdays {
+                days: self.days.clone(),
+            })
+            .filter(|_| !self.days.is_empty()),
+            ScheduleMode::Pattern => {
+                let on = self.on.trim().parse::<u32>().ok().filter(|n| *n > 0)?;
+                let off = self.off.trim().parse::<u32>().ok()?;
+                let start = parse_date(&self.start)?;
+                Some(Schedule::Pattern { on, off, start })
+            }
+        }
+    }
+}
+
+pub struct ScheduleViewModel {
+    pub input: ScheduleInput,
+}
+
+pub fn create_schedule_page<'a>(view_model: ScheduleViewModel) -> impl Into<Element<'a, Message>> {
+    let is_valid = view_model.input.to_schedule().is_some();
+
+    Column::new()
+        .push(create_body(view_model.input))
+        .push(create_footer(is_valid))
+}
+
+fn create_body<'a>(input: ScheduleInput) -> impl Into<Element<'a, Message>> {
+    let mode_pick = pick_list(
+        ScheduleMode::ALL,
+        Some(input.mode),
+        Message::ScheduleModeSelection,
+    )
+    .text_size(14);
+    let mode_row = Row::new()
+        .push(text("Schedule").size(14))
+        .push(horizontal_space())
+        .push(mode_pick)
+        .align_y(Vertical::Center);
+
+    let details: Option<Element<'a, Message>> = match input.mode {
+        ScheduleMode::Off => None,
+        ScheduleMode::Weekdays => Some(
+            WEEKDAYS
+                .iter()
+                .fold(Row::new(), |row: Row<'a, Message>, day| {
+                    let day = *day;
+                    row.push(
+                        checkbox(day.to_string(), input.days.contains(&day))
+                            .on_toggle(move |c| Message::ScheduleDayToggle(day, c))
+                            .text_size(12),
+                    )
+                })
+                .spacing(SPACING_S)
+                .into(),
+        ),
+        ScheduleMode::Pattern => Some(
+            Row::new()
+                .push(
+                    text_input("1", &input.on)
+                        .on_input(Message::ScheduleOnInput)
+                        .size(14)
+                        .width(NUMBER_INPUT_WIDTH),
+                )
+                .push(text("on").size(14))
+                .push(
+                    text_input("1", &input.off)
+                        .on_input(Message::ScheduleOffInput)
+                        .size(14)
+                        .width(NUMBER_INPUT_WIDTH),
+                )
+                .push(text("off, from").size(14))
+                .push(
+                    text_input("YYYY-MM-DD", &input.start)
+                        .on_input(Message::ScheduleStartInput)
+                        .size(14),
+                )
+                .spacing(SPACING_S)
+                .align_y(Vertical::Center)
+                .into(),
+        ),
+    };
+
+    let policy_pick = pick_list(
+        CatchUpPolicy::ALL,
+        Some(input.policy),
+        Message::CatchUpPolicySelection,
+    )
+    .text_size(14);
+    let policy_row = (input.mode != ScheduleMode::Off).then(|| {
+        Row::new()
+            .push(text("Missed days").size(14))
+            .push(horizontal_space())
+            .push(policy_pick)
+            .align_y(Vertical::Center)
+    });
+
+    Column::new()
+        .push(mode_row)
+        .push_maybe(details)
+        .push_maybe(policy_row)
+        .spacing(SPACING_M)
+        .padding(SPACING_M)
+        .height(WINDOW_HEIGHT - FOOTER_HEIGHT)
+}
+
+fn create_footer<'a>(is_valid: bool) -> impl Into<Element<'a, Message>> {
+    let save_btn = button("Save").on_press_maybe(is_valid.then_some(Message::SaveSchedule));
+    let cancel_btn = button("Cancel").on_press(Message::CloseSchedule);
+    let row = Row::new()
+        .push(horizontal_space())
+        .push(save_btn)
+        .push(cancel_btn)
+        .spacing(SPACING_S);
+
+    center(row)
+        .height(FOOTER_HEIGHT)
+        .padding(Padding::ZERO.right(SPACING_M))
+        .dev_background()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schedule::{Schedule, ScheduleSettings};
+    use crate::ui::schedule_page::{ScheduleInput, ScheduleMode};
+    use chrono::{NaiveDate, Weekday};
+
+    fn create_input(mode: ScheduleMode) -> ScheduleInput {
+        let today = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap();
+        ScheduleInput {
+            mode,
+            ..ScheduleInput::new(&ScheduleSettings::default(), today)
+        }
+    }
+
+    #[test]
+    fn test_to_schedule_given_no_weekdays_should_return_none() {
+        let mut input = create_input(ScheduleMode::Weekdays);
+        input.days.clear();
+
+        assert_eq!(None, input.to_schedule())
+    }
+
+    #[test]
+    fn test_to_schedule_given_zero_on_days_should_return_none() {
+        let mut input = create_input(ScheduleMode::Pattern);
+        input.on = String::from("0");
+
+        assert_eq!(None, input.to_schedule())
+    }
+
+    #[test]
+    fn test_to_schedule_given_pattern_should_parse_inputs() {
+        let mut input = create_input(ScheduleMode::Pattern);
+        input.on = String::from("3");
+        input.off = String::from(" 1 ");
+
+        assert_eq!(
+            Some(Schedule::Pattern {
+                on: 3,
+                off: 1,
+                start: NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()
+            }),
+            input.to_schedule()
+        )
+    }
+
+    #[test]
+    fn test_toggle_day_should_keep_week_order() {
+        let mut input = create_input(ScheduleMode::Weekdays);
+
+        input.toggle_day(Weekday::Tue, true);
+
+        assert_eq!(
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Fri],
+            input.days
+        )
+    }
+}
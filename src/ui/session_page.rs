@@ -0,0 +1,242 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::timer::{TimerStatus, format_duration};
+use crate::ui::timer_panel::{TimerViewModel, create_timer_panel};
+use crate::ui::{SPACING_M, SPACING_S, WINDOW_HEIGHT};
+use crate::workout::{Exercise, Load, LoadUnit};
+use iced::alignment::Vertical;
+use iced::widget::{Column, Row, button, center, horizontal_space, text, text_input};
+use iced::{Element, Length, Padding};
+
+const FOOTER_HEIGHT: f32 = 50.0;
+const TIMER_HEIGHT: f32 = 40.0;
+const NUMBER_INPUT_WIDTH: f32 = 70.0;
+
+/// What was actually done in the current set, prefilled with the targets.
+#[derive(Debug, Clone, Default)]
+pub struct SessionInput {
+    pub reps: String,
+    pub load: String,
+    pub unit: Option<LoadUnit>,
+}
+
+impl SessionInput {
+    pub fn new(exercise: Option<&Exercise>) -> SessionInput {
+        let load = exercise.and_then(|e| e.load.as_ref());
+
+        SessionInput {
+            reps: exercise
+                .and_then(|e| e.reps)
+                .map_or(String::new(), |r| r.to_string()),
+            load: load.map_or(String::new(), |l| l.amount.to_string()),
+            unit: load.map(|l| l.unit),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.get_reps().is_some() && self.get_load().is_some()
+    }
+
+    /// Returns `None` for invalid input and `Some(None)` when nothing is entered.
+    pub fn get_reps(&self) -> Option<Option<u32>> {
+        let reps = self.reps.trim();
+        if reps.is_empty() {
+            return Some(None);
+        }
+
+        reps.parse::<u32>().ok().map(Some)
+    }
+
+    /// Returns `None` for invalid input and `Some(None)` when nothing is entered.
+    pub fn get_load(&self) -> Option<Option<Load>> {
+        let load = self.load.trim();
+        if load.is_empty() {
+            return Some(None);
+        }
+
+        load.parse::<f32>()
+            .ok()
+            .filter(|a| a.is_finite() && *a >= 0.0)
+            .map(|amount| {
+                Some(Load {
+                    amount,
+                    unit: self.unit.unwrap_or(LoadUnit::Kg),
+                })
+            })
+    }
+}
+
+pub struct SessionViewModel {
+    pub workout: String,
+    pub exercise: Option<String>,
+    pub targets: String,
+    pub set: u32,
+    pub sets: u32,
+    pub step: usize,
+    pub steps: usize,
+    pub input: SessionInput,
+    pub logged: String,
+    pub can_finish: bool,
+    /// Status of the rest countdown between sets, `None` when not resting.
+    pub rest: Option<TimerStatus>,
+    pub timer: TimerViewModel,
+}
+
+pub fn create_session_page<'a>(view_model: SessionViewModel) -> impl Into<Element<'a, Message>> {
+    Column::new()
+        .push(create_body(
+            create_title(
+                view_model.workout,
+                (view_model.step, view_model.steps),
+                view_model.rest,
+            ),
+            view_model.exercise,
+            view_model.targets,
+            (view_model.set, view_model.sets),
+            view_model.input,
+            view_model.logged,
+        ))
+        .push(create_timer_panel(view_model.timer))
+        .push(create_footer(view_model.can_finish))
+}
+
+fn create_title<'a>(
+    workout: String,
+    (step, steps): (usize, usize),
+    rest: Option<TimerStatus>,
+) -> Row<'a, Message> {
+    let rest = rest.map(|status| {
+        let label = status.remaining.map_or(String::from("Rest over"), |r| {
+            format!("Rest {}", format_duration(r))
+        });
+        text(label).size(14)
+    });
+
+    Row::new()
+        .push(text(workout).size(14))
+        .push(horizontal_space())
+        .push_maybe(rest)
+        .push(text(format!("{} / {}", step.min(steps), steps)).size(14))
+        .spacing(SPACING_M)
+}
+
+fn create_body<'a>(
+    title: Row<'a, Message>,
+    exercise: Option<String>,
+    targets: String,
+    (set, sets): (u32, u32),
+    input: SessionInput,
+    logged: String,
+) -> impl Into<Element<'a, Message>> {
+    let current: Element<'a, Message> = match exercise {
+        None => center(text("All sets done").size(22)).into(),
+        Some(exercise) => {
+            let unit = input.unit.unwrap_or(LoadUnit::Kg).to_string();
+            let log_btn =
+                button("Log set").on_press_maybe(input.is_valid().then_some(Message::LogSet));
+            let skip_btn = button("Skip set").on_press(Message::SkipSet);
+            let inputs = Row::new()
+                .push(
+                    text_input("reps", &input.reps)
+                        .on_input(Message::SessionRepsInput)
+                        .on_submit(Message::LogSet)
+                        .width(NUMBER_INPUT_WIDTH),
+                )
+                .push(text("reps"))
+                .push(
+                    text_input("load", &input.load)
+                        .on_input(Message::SessionLoadInput)
+                        .on_submit(Message::LogSet)
+                        .width(NUMBER_INPUT_WIDTH),
+                )
+                .push(text(unit))
+                .push(horizontal_space())
+                .push(log_btn)
+                .push(skip_btn)
+                .spacing(SPACING_S)
+                .align_y(Vertical::Center);
+
+            Column::new()
+                .push(
+                    Row::new()
+                        .push(text(exercise).size(22))
+                        .push(horizontal_space())
+                        .push(text(format!("Set {} of {}", set, sets)).size(16))
+                        .align_y(Vertical::Center),
+                )
+                .push(text(targets).size(14))
+                .push(inputs)
+                .spacing(SPACING_M)
+                .into()
+        }
+    };
+
+    Column::new()
+        .push(title)
+        .push(current)
+        .push(text(logged).size(12))
+        .spacing(SPACING_M)
+        .padding(SPACING_M)
+        .width(Length::Fill)
+        .height(WINDOW_HEIGHT - TIMER_HEIGHT - FOOTER_HEIGHT)
+}
+
+fn create_footer<'a>(can_finish: bool) -> impl Into<Element<'a, Message>> {
+    let finish_btn = button("Finish").on_press_maybe(can_finish.then_some(Message::FinishSession));
+    let cancel_btn = button("Cancel").on_press(Message::CancelSession);
+    let row = Row::new()
+        .push(horizontal_space())
+        .push(finish_btn)
+        .push(cancel_btn)
+        .spacing(SPACING_S);
+
+    center(row)
+        .height(FOOTER_HEIGHT)
+        .padding(Padding::ZERO.right(SPACING_M))
+        .dev_background()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::session_page::SessionInput;
+    use crate::workout::{Exercise, Load, LoadUnit};
+
+    #[test]
+    fn test_new_should_prefill_targets() {
+        let exercise = Exercise::parse("Squat 5x5 @100kg").unwrap();
+
+        let input = SessionInput::new(Some(&exercise));
+
+        assert_eq!(("5", "100"), (input.reps.as_str(), input.load.as_str()))
+    }
+
+    #[test]
+    fn test_get_load_given_exercise_unit_should_keep_unit() {
+        let exercise = Exercise::parse("Press 3x8 @95lb").unwrap();
+        let mut input = SessionInput::new(Some(&exercise));
+        input.load = String::from("100");
+
+        assert_eq!(
+            Some(Some(Load {
+                amount: 100.0,
+                unit: LoadUnit::Lb
+            })),
+            input.get_load()
+        )
+    }
+
+    #[test]
+    fn test_is_valid_given_empty_input_should_be_true() {
+        assert!(SessionInput::new(None).is_valid())
+    }
+
+    #[test]
+    fn test_is_valid_given_negative_load_should_be_false() {
+        let input = SessionInput {
+            load: String::from("-5"),
+            ..SessionInput::default()
+        };
+
+        assert!(!input.is_valid())
+    }
+}
@@ -1,78 +1,266 @@
 use crate::Message::WorkoutSelection;
 use crate::helper::ContainerExtensions;
-use crate::ui::{SPACING_M, SPACING_S, SPACING_X, WINDOW_HEIGHT, WINDOW_WIDTH};
-use crate::{Message, OperationFlags, Workout};
+use crate::ordering::OrderingMode;
+use crate::ui::{LabelFilterOption, SPACING_M, SPACING_S, SPACING_X, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::workout::Workout;
+use crate::{Message, OperationFlags};
+use iced::alignment::Vertical;
 use iced::widget::scrollable::{Direction, Scrollbar};
+use iced::widget::text::Span;
 use iced::widget::{
-    Column, Container, Row, Scrollable, Space, button, center, horizontal_space, text, text_input,
+    Column, Container, Row, Scrollable, Space, button, center, checkbox, horizontal_space,
+    mouse_area, pick_list, rich_text, span, text, text_input,
 };
-use iced::{Border, Color, Element, Length, Padding};
+use iced::{Border, Color, Element, Font, Length, Padding};
+use std::collections::HashMap;
+use uuid::Uuid;
 
 const FOOTER_HEIGHT: f32 = 50.0;
+const PROGRAM_BAR_HEIGHT: f32 = 40.0;
+const BODY_HEIGHT: f32 = WINDOW_HEIGHT - FOOTER_HEIGHT - PROGRAM_BAR_HEIGHT;
+const REPEAT_COLOR: Color = Color::from_rgb(0.6, 0.7, 0.9);
+const HIGHLIGHT_COLOR: Color = Color::from_rgb(1.0, 0.8, 0.3);
+const DROP_COLOR: Color = Color::from_rgb(0.4, 0.8, 0.5);
+
+/// A workout shown in the list, which may be narrowed down by the filter and search.
+pub struct ListItem {
+    pub workout: Workout,
+    /// Numbers the entry when its text appears more than once in the rotation.
+    pub occurrence: Option<usize>,
+    pub is_linked: bool,
+    /// Char indices of the text matched by the search.
+    pub highlights: Vec<usize>,
+}
 
 pub struct SettingsViewModel<'a> {
-    pub workouts: Vec<Workout>,
+    pub items: Vec<ListItem>,
+    pub search_input: String,
+    pub filter_options: Vec<LabelFilterOption>,
+    /// Limits the list to workouts with a category or tag.
+    pub filter: LabelFilterOption,
+    pub skip_counts: HashMap<Uuid, usize>,
+    pub ordering: OrderingMode,
+    pub allow_duplicates: bool,
+    pub current_id: Option<Uuid>,
+    /// Workouts checked to be moved together.
+    pub marked: Vec<Uuid>,
+    /// The workout a dragged entry is currently over.
+    pub drag_target: Option<Uuid>,
     pub workout_selection: Option<Workout>,
+    pub is_selection_linked: bool,
     pub workout_input: Option<String>,
+    pub program_input: Option<String>,
     pub operation_flags: &'a OperationFlags,
 }
 
 pub fn create_settings_page<'a>(view_model: SettingsViewModel) -> impl Into<Element<'a, Message>> {
     Column::new()
+        .push(create_program_bar(
+            view_model.program_input,
+            view_model.operation_flags,
+        ))
         .push(create_body(
-            view_model.workouts,
-            view_model.workout_selection,
+            (view_model.items, view_model.search_input),
+            view_model.skip_counts,
+            (
+                view_model.current_id,
+                view_model.marked,
+                view_model.drag_target,
+            ),
+            (view_model.workout_selection, view_model.is_selection_linked),
             view_model.workout_input,
+            (view_model.filter_options, view_model.filter),
+            view_model.operation_flags,
+        ))
+        .push(create_footer(
+            view_model.ordering,
+            view_model.allow_duplicates,
             view_model.operation_flags,
         ))
-        .push(create_footer())
 }
 
 fn create_body<'a>(
-    workouts: Vec<Workout>,
-    workout_selection: Option<Workout>,
+    (items, search_input): (Vec<ListItem>, String),
+    skip_counts: HashMap<Uuid, usize>,
+    (current_id, marked, drag_target): (Option<Uuid>, Vec<Uuid>, Option<Uuid>),
+    (workout_selection, is_selection_linked): (Option<Workout>, bool),
     workout_input: Option<String>,
+    (filter_options, filter): (Vec<LabelFilterOption>, LabelFilterOption),
     flags: &OperationFlags,
 ) -> impl Into<Element<'a, Message>> {
     Row::new()
-        .push(create_workouts_list(workouts, workout_selection))
-        .push(create_button_panel(workout_input, flags))
+        .push(create_workouts_list(
+            items,
+            search_input,
+            skip_counts,
+            (current_id, marked, drag_target),
+            workout_selection,
+        ))
+        .push(create_button_panel(
+            workout_input,
+            is_selection_linked,
+            (filter_options, filter),
+            flags,
+        ))
         .padding(SPACING_S)
-        .height(WINDOW_HEIGHT - FOOTER_HEIGHT)
+        .height(BODY_HEIGHT)
+}
+
+fn create_program_bar<'a>(
+    program_input: Option<String>,
+    flags: &OperationFlags,
+) -> impl Into<Element<'a, Message>> {
+    let input_value = program_input.unwrap_or_default();
+    let name_message = |message: Message| {
+        flags
+            .contains(OperationFlags::CanNameProgram)
+            .then_some(message)
+    };
+    let name_input = text_input("Program name", &input_value)
+        .on_input(|s| Message::ProgramInput(Some(s).filter(|s| !s.is_empty())))
+        .size(14);
+    let new_btn = button(text("New").size(14)).on_press_maybe(name_message(Message::CreateProgram));
+    let duplicate_btn =
+        button(text("Duplicate").size(14)).on_press_maybe(name_message(Message::DuplicateProgram));
+    let rename_btn =
+        button(text("Rename").size(14)).on_press_maybe(name_message(Message::RenameProgram));
+    let delete_btn = button(text("Delete").size(14)).on_press_maybe(
+        flags
+            .contains(OperationFlags::CanDeleteProgram)
+            .then_some(Message::InitiateProgramDeletion),
+    );
+
+    Row::new()
+        .push(name_input)
+        .push(new_btn)
+        .push(duplicate_btn)
+        .push(rename_btn)
+        .push(delete_btn)
+        .spacing(SPACING_S)
+        .padding(Padding::from([SPACING_S, SPACING_M]))
+        .height(PROGRAM_BAR_HEIGHT)
 }
 
 fn create_workouts_list<'a>(
-    workouts: Vec<Workout>,
+    items: Vec<ListItem>,
+    search_input: String,
+    skip_counts: HashMap<Uuid, usize>,
+    (current_id, marked, drag_target): (Option<Uuid>, Vec<Uuid>, Option<Uuid>),
     workout_selection: Option<Workout>,
 ) -> impl Into<Element<'a, Message>> {
-    let column = workouts
+    let search = text_input("Search", &search_input)
+        .on_input(Message::SearchInput)
+        .on_submit(Message::SearchSubmit)
+        .size(14);
+    let is_searching = !search_input.trim().is_empty();
+    let is_empty = items.is_empty();
+    let column = items
         .into_iter()
-        .fold(
-            Column::new(),
-            |column: Column<'a, Message>, workout: Workout| {
-                let is_selected = workout_selection
-                    .as_ref()
-                    .is_some_and(|w| w.id == workout.id);
-                let button = button(text(workout.text.clone()))
-                    .width(Length::Fill)
-                    .style(move |_, _| get_list_item_style(is_selected))
-                    .on_press(WorkoutSelection(Some(workout)));
-                column.push(button)
-            },
-        )
+        .fold(Column::new(), |column: Column<'a, Message>, item| {
+            let workout = item.workout;
+            let is_selected = workout_selection
+                .as_ref()
+                .is_some_and(|w| w.id == workout.id);
+            let marker = if current_id == Some(workout.id) {
+                "\u{25B8} "
+            } else {
+                ""
+            };
+            let mut label = Row::new().push(create_highlighted_text(
+                marker,
+                &workout.text,
+                &item.highlights,
+            ));
+            if let Some(occurrence) = item.occurrence {
+                let repeat = if item.is_linked {
+                    format!("#{} linked", occurrence)
+                } else {
+                    format!("#{}", occurrence)
+                };
+                label = label
+                    .push(Space::with_width(SPACING_S))
+                    .push(text(repeat).size(12).color(REPEAT_COLOR));
+            }
+            if let Some(skips) = skip_counts.get(&workout.id) {
+                label = label
+                    .push(horizontal_space())
+                    .push(text(format!("{} skipped", skips)).size(12));
+            }
+            let id = workout.id;
+            let is_drop_target = drag_target == Some(id);
+            let mark_check = checkbox("", marked.contains(&id))
+                .on_toggle(move |is_marked| Message::MarkWorkout(id, is_marked));
+            let handle = mouse_area(text("\u{2261}")).on_press(Message::DragWorkout(id));
+            let button = button(label)
+                .width(Length::Fill)
+                .style(move |_, _| get_list_item_style(is_selected, is_drop_target))
+                .on_press(WorkoutSelection(Some(workout)));
+            let row = Row::new()
+                .push(handle)
+                .push(mark_check)
+                .push(button)
+                .spacing(SPACING_S)
+                .align_y(Vertical::Center);
+            column.push(
+                mouse_area(row)
+                    .on_enter(Message::DragOverWorkout(id))
+                    .on_exit(Message::DragOutWorkout(id)),
+            )
+        })
+        .push_maybe((is_empty && is_searching).then(|| text("No matches").size(14)))
         .padding(Padding::ZERO.right(SPACING_X))
         .spacing(2);
-    let scrollable = Scrollable::with_direction(column, Direction::Vertical(Scrollbar::default()));
-    let container = Container::new(scrollable)
-        .width((WINDOW_WIDTH / 2.0) - 2.0 * SPACING_S)
-        .height(WINDOW_HEIGHT - FOOTER_HEIGHT - 2.0 * SPACING_S)
-        .background(20, 20, 20);
+    let scrollable = Scrollable::with_direction(column, Direction::Vertical(Scrollbar::default()))
+        .height(Length::Fill);
+    let container = Container::new(
+        Column::new()
+            .push(search)
+            .push(scrollable)
+            .spacing(SPACING_S),
+    )
+    .width((WINDOW_WIDTH / 2.0) - 2.0 * SPACING_S)
+    .height(BODY_HEIGHT - 2.0 * SPACING_S)
+    .background(20, 20, 20);
 
     Container::new(container).padding(Padding::new(SPACING_S))
 }
 
+/// Shows the text with the characters matched by the search in the highlight color.
+fn create_highlighted_text<'a>(
+    marker: &str,
+    workout_text: &str,
+    highlights: &[usize],
+) -> impl Into<Element<'a, Message>> {
+    let mut spans: Vec<Span<'a, Message, Font>> = vec![span(marker.to_owned())];
+    let mut run = String::new();
+    let mut is_run_highlighted = false;
+    for (i, c) in workout_text.chars().enumerate() {
+        let is_highlighted = highlights.contains(&i);
+        if is_highlighted != is_run_highlighted && !run.is_empty() {
+            spans.push(create_span(std::mem::take(&mut run), is_run_highlighted));
+        }
+        is_run_highlighted = is_highlighted;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(create_span(run, is_run_highlighted));
+    }
+
+    rich_text(spans).color(Color::WHITE)
+}
+
+fn create_span<'a>(content: String, is_highlighted: bool) -> Span<'a, Message, Font> {
+    if is_highlighted {
+        span(content).color(HIGHLIGHT_COLOR).underline(true)
+    } else {
+        span(content)
+    }
+}
+
 fn create_button_panel<'a>(
     workout_input: Option<String>,
+    is_selection_linked: bool,
+    (filter_options, filter): (Vec<LabelFilterOption>, LabelFilterOption),
     flags: &OperationFlags,
 ) -> impl Into<Element<'a, Message>> {
     let input_value = workout_input.clone().map_or("".to_owned(), move |s| s);
@@ -103,19 +291,60 @@ fn create_button_panel<'a>(
     let remove_btn = button(text("X")).on_press_maybe(
         flags
             .contains(OperationFlags::CanDelete)
-            .then_some(Message::InitiateWorkoutDeletion),
+            .then_some(Message::DeleteWorkout),
+    );
+    let current_btn = button(text("Current")).on_press_maybe(
+        flags
+            .contains(OperationFlags::CanSetCurrent)
+            .then_some(Message::SetCurrentWorkout),
+    );
+    let move_top_btn = button(text("Top")).on_press_maybe(
+        flags
+            .contains(OperationFlags::CanMoveToTop)
+            .then_some(Message::MoveWorkoutsToTop),
+    );
+    let move_bottom_btn = button(text("Bottom")).on_press_maybe(
+        flags
+            .contains(OperationFlags::CanMoveToBottom)
+            .then_some(Message::MoveWorkoutsToBottom),
     );
     let edit_row = Row::new()
         .push(move_up_btn)
         .push(move_down_btn)
+        .push(move_top_btn)
+        .push(move_bottom_btn)
         .push(Space::with_width(SPACING_M))
         .push(remove_btn)
+        .push(current_btn)
         .spacing(SPACING_S);
+    let filter_pick = pick_list(
+        filter_options,
+        Some(filter),
+        Message::SettingsFilterSelection,
+    )
+    .text_size(14);
+    let exercises_btn = button(text("Details")).on_press_maybe(
+        flags
+            .contains(OperationFlags::CanEditExercises)
+            .then_some(Message::OpenExerciseEditor),
+    );
     let clear_btn = button(text("Clear")).on_press_maybe(
         flags
             .contains(OperationFlags::CanClear)
-            .then_some(Message::InitiateClearance),
+            .then_some(Message::ClearWorkouts),
     );
+    let link_label = if is_selection_linked {
+        "Unlink"
+    } else {
+        "Link"
+    };
+    let link_btn = button(text(link_label)).on_press_maybe(
+        flags
+            .contains(OperationFlags::CanLink)
+            .then_some(Message::ToggleLink),
+    );
+    let transfer_btn = button(text("Import / Export")).on_press(Message::OpenTransfer);
+    let schedule_btn = button(text("Schedule")).on_press(Message::OpenSchedule);
 
     Column::new()
         .push(add_input)
@@ -123,14 +352,57 @@ fn create_button_panel<'a>(
         .push(Space::with_height(SPACING_M))
         .push(edit_row)
         .push(Space::with_height(SPACING_M))
-        .push(clear_btn)
+        .push(
+            Row::new()
+                .push(exercises_btn)
+                .push(link_btn)
+                .push(clear_btn)
+                .spacing(SPACING_S),
+        )
+        .push(
+            Row::new()
+                .push(schedule_btn)
+                .push(transfer_btn)
+                .spacing(SPACING_S),
+        )
+        .push(filter_pick)
         .padding(SPACING_S)
         .spacing(SPACING_S)
 }
 
-fn create_footer<'a>() -> impl Into<Element<'a, Message>> {
+fn create_footer<'a>(
+    ordering: OrderingMode,
+    allow_duplicates: bool,
+    flags: &OperationFlags,
+) -> impl Into<Element<'a, Message>> {
+    let ordering_pick = pick_list(
+        OrderingMode::ALL,
+        Some(ordering),
+        Message::OrderingSelection,
+    )
+    .text_size(14);
+    let duplicates_check = checkbox("Duplicates", allow_duplicates)
+        .on_toggle(Message::AllowDuplicatesToggle)
+        .text_size(14);
+    let undo_btn = button("Undo").on_press_maybe(
+        flags
+            .contains(OperationFlags::CanUndo)
+            .then_some(Message::Undo),
+    );
+    let redo_btn = button("Redo").on_press_maybe(
+        flags
+            .contains(OperationFlags::CanRedo)
+            .then_some(Message::Redo),
+    );
     let ok_btn = button("Ok").on_press(Message::CloseSettings);
-    let row = Row::new().push(horizontal_space()).push(ok_btn);
+    let row = Row::new()
+        .push(undo_btn)
+        .push(redo_btn)
+        .push(horizontal_space())
+        .push(duplicates_check)
+        .push(ordering_pick)
+        .push(ok_btn)
+        .spacing(SPACING_S);
 
     center(row)
         .height(FOOTER_HEIGHT)
@@ -138,7 +410,7 @@ fn create_footer<'a>() -> impl Into<Element<'a, Message>> {
         .dev_background()
 }
 
-fn get_list_item_style(is_selected: bool) -> button::Style {
+fn get_list_item_style(is_selected: bool, is_drop_target: bool) -> button::Style {
     let background_color = if is_selected {
         Color {
             a: 0.1,
@@ -152,8 +424,12 @@ fn get_list_item_style(is_selected: bool) -> button::Style {
         background: Some(background_color.into()),
         text_color: Color::WHITE,
         border: Border {
-            width: 1.0,
-            color: Color::WHITE,
+            width: if is_drop_target { 2.0 } else { 1.0 },
+            color: if is_drop_target {
+                DROP_COLOR
+            } else {
+                Color::WHITE
+            },
             radius: 1.0.into(),
         },
         ..button::Style::default()
@@ -0,0 +1,125 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::stats::Streaks;
+use crate::ui::chart::{Chart, ChartKind, ChartPoint};
+use crate::ui::{SPACING_M, SPACING_S, WINDOW_HEIGHT};
+use iced::alignment::Vertical;
+use iced::widget::{
+    Canvas, Column, Container, Row, button, center, horizontal_space, pick_list, text,
+};
+use iced::{Color, Element, Length, Padding};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+const SUMMARY_HEIGHT: f32 = 40.0;
+const FOOTER_HEIGHT: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StatsChart {
+    #[default]
+    SessionsPerWeek,
+    CompletionRate,
+    Volume,
+    TopLoad,
+}
+
+impl StatsChart {
+    pub const ALL: [StatsChart; 4] = [
+        StatsChart::SessionsPerWeek,
+        StatsChart::CompletionRate,
+        StatsChart::Volume,
+        StatsChart::TopLoad,
+    ];
+
+    fn get_kind(&self) -> ChartKind {
+        match self {
+            StatsChart::SessionsPerWeek | StatsChart::CompletionRate => ChartKind::Bar,
+            StatsChart::Volume | StatsChart::TopLoad => ChartKind::Line,
+        }
+    }
+
+    fn get_color(&self) -> Color {
+        match self {
+            StatsChart::SessionsPerWeek => Color::from_rgb8(100, 160, 240),
+            StatsChart::CompletionRate => Color::from_rgb8(90, 200, 120),
+            StatsChart::Volume | StatsChart::TopLoad => Color::from_rgb8(240, 170, 80),
+        }
+    }
+}
+
+impl Display for StatsChart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsChart::SessionsPerWeek => write!(f, "Sessions per week"),
+            StatsChart::CompletionRate => write!(f, "Completion rate (%)"),
+            StatsChart::Volume => write!(f, "Volume (kg)"),
+            StatsChart::TopLoad => write!(f, "Top load (kg)"),
+        }
+    }
+}
+
+pub struct StatsViewModel {
+    pub streaks: Streaks,
+    pub completed: usize,
+    pub chart: StatsChart,
+    pub points: Vec<ChartPoint>,
+}
+
+pub fn create_stats_page<'a>(view_model: StatsViewModel) -> impl Into<Element<'a, Message>> {
+    Column::new()
+        .push(create_summary(
+            view_model.streaks,
+            view_model.completed,
+            view_model.chart,
+        ))
+        .push(create_chart(view_model.chart, view_model.points))
+        .push(create_footer())
+}
+
+fn create_summary<'a>(
+    streaks: Streaks,
+    completed: usize,
+    chart: StatsChart,
+) -> impl Into<Element<'a, Message>> {
+    let summary = format!(
+        "{} done \u{b7} streak {} (best {})",
+        completed, streaks.current, streaks.longest
+    );
+    let chart_pick =
+        pick_list(StatsChart::ALL, Some(chart), Message::StatsChartSelection).text_size(14);
+
+    Row::new()
+        .push(text(summary).size(14))
+        .push(horizontal_space())
+        .push(chart_pick)
+        .spacing(SPACING_S)
+        .padding(SPACING_S)
+        .align_y(Vertical::Center)
+        .height(SUMMARY_HEIGHT)
+}
+
+fn create_chart<'a>(chart: StatsChart, points: Vec<ChartPoint>) -> impl Into<Element<'a, Message>> {
+    let canvas = Canvas::new(Chart {
+        kind: chart.get_kind(),
+        points,
+        color: chart.get_color(),
+    })
+    .width(Length::Fill)
+    .height(Length::Fill);
+
+    Container::new(canvas)
+        .width(Length::Fill)
+        .height(WINDOW_HEIGHT - SUMMARY_HEIGHT - FOOTER_HEIGHT)
+        .padding(SPACING_M)
+        .background(20, 20, 20)
+}
+
+fn create_footer<'a>() -> impl Into<Element<'a, Message>> {
+    let ok_btn = button("Ok").on_press(Message::CloseStats);
+    let row = Row::new().push(horizontal_space()).push(ok_btn);
+
+    center(row)
+        .height(FOOTER_HEIGHT)
+        .padding(Padding::ZERO.right(SPACING_M))
+        .dev_background()
+}
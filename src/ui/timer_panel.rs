@@ -0,0 +1,86 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::timer::{PhaseKind, TimerMode, TimerStatus, format_duration};
+use crate::ui::{SPACING_M, SPACING_S};
+use iced::alignment::Vertical;
+use iced::widget::{Row, button, center, horizontal_space, pick_list, text, text_input};
+use iced::{Color, Element, Padding};
+
+const PANEL_HEIGHT: f32 = 40.0;
+const AMOUNT_INPUT_WIDTH: f32 = 50.0;
+
+pub struct TimerViewModel {
+    pub mode: TimerMode,
+    pub amount: String,
+    pub is_amount_valid: bool,
+    pub status: TimerStatus,
+    pub is_running: bool,
+    pub is_cued: bool,
+}
+
+pub fn create_timer_panel<'a>(view_model: TimerViewModel) -> impl Into<Element<'a, Message>> {
+    let mode_pick = pick_list(
+        TimerMode::ALL,
+        Some(view_model.mode),
+        Message::TimerModeSelection,
+    )
+    .text_size(14);
+    let amount = view_model.mode.get_amount_unit().map(|unit| {
+        Row::new()
+            .push(
+                text_input("", &view_model.amount)
+                    .on_input_maybe((!view_model.is_running).then_some(Message::TimerAmountInput))
+                    .size(14)
+                    .width(AMOUNT_INPUT_WIDTH),
+            )
+            .push(text(unit).size(14))
+            .spacing(SPACING_S)
+            .align_y(Vertical::Center)
+    });
+
+    let status = view_model.status;
+    let (label, color) = match status.kind {
+        Some(PhaseKind::Work) => (PhaseKind::Work.to_string(), Color::from_rgb8(90, 200, 120)),
+        Some(PhaseKind::Rest) => (PhaseKind::Rest.to_string(), Color::from_rgb8(100, 160, 240)),
+        None => (String::from("Done"), Color::from_rgb8(230, 90, 90)),
+    };
+    let label = if status.rounds > 1 {
+        format!("{} {}/{}", label, status.round, status.rounds)
+    } else {
+        label
+    };
+    let time = status.remaining.unwrap_or(status.elapsed);
+
+    let toggle_btn = button(
+        text(if view_model.is_running {
+            "Pause"
+        } else {
+            "Start"
+        })
+        .size(14),
+    )
+    .on_press_maybe(
+        (view_model.is_amount_valid && status.kind.is_some()).then_some(Message::ToggleTimer),
+    );
+    let reset_btn = button(text("Reset").size(14)).on_press(Message::ResetTimer);
+
+    let row = Row::new()
+        .push(mode_pick)
+        .push_maybe(amount)
+        .push(horizontal_space())
+        .push(text(label).size(14))
+        .push(text(format_duration(time)).size(20).color(color))
+        .push(toggle_btn)
+        .push(reset_btn)
+        .spacing(SPACING_M)
+        .align_y(Vertical::Center);
+
+    let panel = center(row)
+        .height(PANEL_HEIGHT)
+        .padding(Padding::ZERO.left(SPACING_M).right(SPACING_M));
+    if view_model.is_cued {
+        panel.background(70, 70, 40)
+    } else {
+        panel.dev_background()
+    }
+}
@@ -0,0 +1,128 @@
+use crate::Message;
+use crate::helper::ContainerExtensions;
+use crate::transfer::TransferFormat;
+use crate::ui::{SPACING_M, SPACING_S, WINDOW_HEIGHT};
+use iced::widget::{Column, Row, button, center, horizontal_space, pick_list, text, text_input};
+use iced::{Element, Padding};
+use std::path::PathBuf;
+
+const FOOTER_HEIGHT: f32 = 50.0;
+const HINT: &str =
+    "CSV and JSON keep exercises and the current workout, text is one workout per line";
+
+#[derive(Debug, Clone, Default)]
+pub struct TransferInput {
+    pub path: String,
+    pub format: Option<TransferFormat>,
+    pub status: Option<String>,
+}
+
+impl TransferInput {
+    /// Picks the format from the file extension unless it is not recognized.
+    pub fn set_path(&mut self, path: String) {
+        if let Some(format) = TransferFormat::from_path(&PathBuf::from(&path)) {
+            self.format = Some(format);
+        }
+        self.path = path;
+    }
+
+    pub fn get_target(&self) -> Option<(PathBuf, TransferFormat)> {
+        let path = self.path.trim();
+        if path.is_empty() {
+            return None;
+        }
+
+        self.format.map(|f| (PathBuf::from(path), f))
+    }
+}
+
+pub struct TransferViewModel {
+    pub program: String,
+    pub input: TransferInput,
+}
+
+pub fn create_transfer_page<'a>(view_model: TransferViewModel) -> impl Into<Element<'a, Message>> {
+    Column::new()
+        .push(create_body(view_model.program, view_model.input))
+        .push(create_footer())
+}
+
+fn create_body<'a>(program: String, input: TransferInput) -> impl Into<Element<'a, Message>> {
+    let has_target = input.get_target().is_some();
+    let path_input = text_input("File path, e.g. workouts.csv", &input.path)
+        .on_input(Message::TransferPathInput);
+    let format_pick = pick_list(
+        TransferFormat::ALL,
+        input.format,
+        Message::TransferFormatSelection,
+    )
+    .placeholder("Format");
+    let import_btn =
+        button(text("Import")).on_press_maybe(has_target.then_some(Message::PreviewImport));
+    let export_btn =
+        button(text("Export")).on_press_maybe(has_target.then_some(Message::ExportWorkouts));
+    let button_row = Row::new()
+        .push(format_pick)
+        .push(horizontal_space())
+        .push(import_btn)
+        .push(export_btn)
+        .spacing(SPACING_S);
+
+    Column::new()
+        .push(text(format!("Workouts of '{}'", program)).size(18))
+        .push(text(HINT).size(12))
+        .push(path_input)
+        .push(button_row)
+        .push_maybe(input.status.map(|s| text(s).size(14)))
+        .spacing(SPACING_S)
+        .padding(SPACING_M)
+        .height(WINDOW_HEIGHT - FOOTER_HEIGHT)
+}
+
+fn create_footer<'a>() -> impl Into<Element<'a, Message>> {
+    let back_btn = button("Back").on_press(Message::CloseTransfer);
+    let row = Row::new().push(horizontal_space()).push(back_btn);
+
+    center(row)
+        .height(FOOTER_HEIGHT)
+        .padding(Padding::ZERO.right(SPACING_M))
+        .dev_background()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transfer::TransferFormat;
+    use crate::ui::transfer_page::TransferInput;
+
+    #[test]
+    fn test_set_path_given_known_extension_should_select_format() {
+        let mut input = TransferInput::default();
+
+        input.set_path(String::from("workouts.JSON"));
+
+        assert_eq!(Some(TransferFormat::Json), input.format)
+    }
+
+    #[test]
+    fn test_set_path_given_unknown_extension_should_keep_format() {
+        let mut input = TransferInput {
+            format: Some(TransferFormat::Text),
+            ..TransferInput::default()
+        };
+
+        input.set_path(String::from("workouts.list"));
+
+        assert_eq!(Some(TransferFormat::Text), input.format)
+    }
+
+    #[test]
+    fn test_get_target_given_blank_path_should_return_none() {
+        let input = TransferInput {
+            path: String::from("  "),
+            format: Some(TransferFormat::Csv),
+            ..TransferInput::default()
+        };
+
+        assert!(input.get_target().is_none())
+    }
+}
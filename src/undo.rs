@@ -0,0 +1,439 @@
+use crate::ordering::OrderingMode;
+use crate::workout::{Workout, get_index_after_move, get_index_after_removal};
+
+const MAX_UNDO_STEPS: usize = 100;
+
+/// Program settings that are undone together with the rotation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RotationSettings {
+    pub ordering: OrderingMode,
+    pub allow_duplicates: bool,
+}
+
+/// A reversible edit of the active rotation.
+#[derive(Debug, Clone)]
+pub enum EditCommand {
+    Insert {
+        position: usize,
+        workout: Workout,
+    },
+    /// Remembers the index before the removal, since removing can move it.
+    Remove {
+        position: usize,
+        workout: Workout,
+        index: usize,
+    },
+    Update {
+        position: usize,
+        before: Workout,
+        after: Workout,
+    },
+    Move {
+        from: usize,
+        to: usize,
+    },
+    SetIndex {
+        before: usize,
+        after: usize,
+    },
+    ReplaceAll {
+        before: (Vec<Workout>, usize),
+        after: (Vec<Workout>, usize),
+    },
+    SetSettings {
+        before: RotationSettings,
+        after: RotationSettings,
+    },
+}
+
+impl EditCommand {
+    pub fn apply(
+        &self,
+        workouts: &mut Vec<Workout>,
+        index: &mut usize,
+        settings: &mut RotationSettings,
+    ) {
+        match self {
+            EditCommand::Insert { position, workout } => {
+                workouts.insert(*position, workout.clone());
+            }
+            EditCommand::Remove { position, .. } => {
+                workouts.remove(*position);
+                *index = get_index_after_removal(*index, *position);
+            }
+            EditCommand::Update {
+                position, after, ..
+            } => workouts[*position] = after.clone(),
+            EditCommand::Move { from, to } => {
+                let workout = workouts.remove(*from);
+                workouts.insert(*to, workout);
+                *index = get_index_after_move(*index, *from, *to);
+            }
+            EditCommand::SetIndex { after, .. } => *index = *after,
+            EditCommand::ReplaceAll { after, .. } => {
+                *workouts = after.0.clone();
+                *index = after.1;
+            }
+            EditCommand::SetSettings { after, .. } => *settings = *after,
+        }
+    }
+
+    pub fn revert(
+        &self,
+        workouts: &mut Vec<Workout>,
+        index: &mut usize,
+        settings: &mut RotationSettings,
+    ) {
+        match self {
+            EditCommand::Insert { position, .. } => {
+                workouts.remove(*position);
+            }
+            EditCommand::Remove {
+                position,
+                workout,
+                index: before,
+            } => {
+                workouts.insert(*position, workout.clone());
+                *index = *before;
+            }
+            EditCommand::Update {
+                position, before, ..
+            } => workouts[*position] = before.clone(),
+            EditCommand::Move { from, to } => {
+                let workout = workouts.remove(*to);
+                workouts.insert(*from, workout);
+                *index = get_index_after_move(*index, *to, *from);
+            }
+            EditCommand::SetIndex { before, .. } => *index = *before,
+            EditCommand::ReplaceAll { before, .. } => {
+                *workouts = before.0.clone();
+                *index = before.1;
+            }
+            EditCommand::SetSettings { before, .. } => *settings = *before,
+        }
+    }
+}
+
+/// Commands that were applied to the rotation, so they can be reverted and applied again.
+#[derive(Debug, Default)]
+pub struct UndoHistory {
+    done: Vec<EditCommand>,
+    undone: Vec<EditCommand>,
+}
+
+impl UndoHistory {
+    pub fn execute(
+        &mut self,
+        command: EditCommand,
+        workouts: &mut Vec<Workout>,
+        index: &mut usize,
+        settings: &mut RotationSettings,
+    ) {
+        command.apply(workouts, index, settings);
+        if self.done.len() == MAX_UNDO_STEPS {
+            self.done.remove(0);
+        }
+        self.done.push(command);
+        self.undone.clear();
+    }
+
+    pub fn undo(
+        &mut self,
+        workouts: &mut Vec<Workout>,
+        index: &mut usize,
+        settings: &mut RotationSettings,
+    ) -> bool {
+        match self.done.pop() {
+            None => false,
+            Some(command) => {
+                command.revert(workouts, index, settings);
+                self.undone.push(command);
+                true
+            }
+        }
+    }
+
+    pub fn redo(
+        &mut self,
+        workouts: &mut Vec<Workout>,
+        index: &mut usize,
+        settings: &mut RotationSettings,
+    ) -> bool {
+        match self.undone.pop() {
+            None => false,
+            Some(command) => {
+                command.apply(workouts, index, settings);
+                self.done.push(command);
+                true
+            }
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ordering::OrderingMode;
+    use crate::undo::{EditCommand, MAX_UNDO_STEPS, RotationSettings, UndoHistory};
+    use crate::workout::Workout;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn create_workouts(texts: &[&str]) -> Vec<Workout> {
+        texts.iter().map(|t| Workout::new(t.to_string())).collect()
+    }
+
+    fn get_texts(workouts: &[Workout]) -> Vec<&str> {
+        workouts.iter().map(|w| w.text.as_str()).collect()
+    }
+
+    fn create_random_command(rng: &mut StdRng, workouts: &[Workout], index: usize) -> EditCommand {
+        let count = workouts.len();
+        match rng.random_range(0..4) {
+            _ if count == 0 => EditCommand::Insert {
+                position: 0,
+                workout: Workout::new(String::from("inserted")),
+            },
+            0 => EditCommand::Insert {
+                position: rng.random_range(0..=count),
+                workout: Workout::new(String::from("inserted")),
+            },
+            1 => {
+                let position = rng.random_range(0..count);
+                EditCommand::Remove {
+                    position,
+                    workout: workouts[position].clone(),
+                    index,
+                }
+            }
+            2 => EditCommand::Move {
+                from: rng.random_range(0..count),
+                to: rng.random_range(0..count),
+            },
+            _ => EditCommand::SetIndex {
+                before: index,
+                after: rng.random_range(0..count),
+            },
+        }
+    }
+
+    fn is_index_in_range(workouts: &[Workout], index: usize) -> bool {
+        index < workouts.len() || (workouts.is_empty() && index == 0)
+    }
+
+    #[test]
+    fn test_execute_given_random_edits_of_large_list_should_keep_index_in_range() {
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut workouts: Vec<_> = (0..300)
+                .map(|i| Workout::new(format!("workout{}", i)))
+                .collect();
+            let mut index = rng.random_range(0..workouts.len());
+            let mut settings = RotationSettings::default();
+            let mut history = UndoHistory::default();
+
+            for _ in 0..MAX_UNDO_STEPS {
+                let command = create_random_command(&mut rng, &workouts, index);
+                history.execute(command, &mut workouts, &mut index, &mut settings);
+                assert!(is_index_in_range(&workouts, index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_given_random_moves_should_keep_active_workout() {
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut workouts: Vec<_> = (0..300)
+                .map(|i| Workout::new(format!("workout{}", i)))
+                .collect();
+            let mut index = rng.random_range(0..workouts.len());
+            let active = workouts[index].id;
+            let mut settings = RotationSettings::default();
+            let mut history = UndoHistory::default();
+
+            for _ in 0..MAX_UNDO_STEPS {
+                let command = EditCommand::Move {
+                    from: rng.random_range(0..workouts.len()),
+                    to: rng.random_range(0..workouts.len()),
+                };
+                history.execute(command, &mut workouts, &mut index, &mut settings);
+                assert_eq!(active, workouts[index].id);
+            }
+            while history.undo(&mut workouts, &mut index, &mut settings) {
+                assert_eq!(active, workouts[index].id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo_given_random_edits_should_restore_list_and_index() {
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut workouts: Vec<_> = (0..200)
+                .map(|i| Workout::new(format!("workout{}", i)))
+                .collect();
+            let mut index = 150;
+            let original = workouts.clone();
+            let mut settings = RotationSettings::default();
+            let mut history = UndoHistory::default();
+
+            for _ in 0..MAX_UNDO_STEPS {
+                let command = create_random_command(&mut rng, &workouts, index);
+                history.execute(command, &mut workouts, &mut index, &mut settings);
+            }
+            while history.undo(&mut workouts, &mut index, &mut settings) {
+                assert!(is_index_in_range(&workouts, index));
+            }
+
+            assert_eq!(get_texts(&original), get_texts(&workouts));
+            assert_eq!(150, index)
+        }
+    }
+
+    #[test]
+    fn test_undo_given_remove_should_restore_workout_and_index() {
+        let mut workouts = create_workouts(&["workout1", "workout2", "workout3"]);
+        let mut index = 2;
+        let mut settings = RotationSettings::default();
+        let mut history = UndoHistory::default();
+        let command = EditCommand::Remove {
+            position: 0,
+            workout: workouts[0].clone(),
+            index,
+        };
+
+        history.execute(command, &mut workouts, &mut index, &mut settings);
+        assert_eq!(1, index);
+        history.undo(&mut workouts, &mut index, &mut settings);
+
+        assert_eq!(
+            vec!["workout1", "workout2", "workout3"],
+            get_texts(&workouts)
+        );
+        assert_eq!(2, index)
+    }
+
+    #[test]
+    fn test_redo_given_undone_move_should_move_again() {
+        let mut workouts = create_workouts(&["workout1", "workout2", "workout3"]);
+        let mut index = 0;
+        let mut settings = RotationSettings::default();
+        let mut history = UndoHistory::default();
+
+        history.execute(
+            EditCommand::Move { from: 0, to: 2 },
+            &mut workouts,
+            &mut index,
+            &mut settings,
+        );
+        history.undo(&mut workouts, &mut index, &mut settings);
+        assert_eq!(
+            vec!["workout1", "workout2", "workout3"],
+            get_texts(&workouts)
+        );
+        history.redo(&mut workouts, &mut index, &mut settings);
+
+        assert_eq!(
+            vec!["workout2", "workout3", "workout1"],
+            get_texts(&workouts)
+        )
+    }
+
+    #[test]
+    fn test_undo_given_replace_all_should_restore_list_and_index() {
+        let mut workouts = create_workouts(&["workout1", "workout2"]);
+        let mut index = 1;
+        let mut settings = RotationSettings::default();
+        let mut history = UndoHistory::default();
+        let command = EditCommand::ReplaceAll {
+            before: (workouts.clone(), index),
+            after: (vec![], 0),
+        };
+
+        history.execute(command, &mut workouts, &mut index, &mut settings);
+        history.undo(&mut workouts, &mut index, &mut settings);
+
+        assert_eq!(vec!["workout1", "workout2"], get_texts(&workouts));
+        assert_eq!(1, index)
+    }
+
+    #[test]
+    fn test_execute_given_undone_commands_should_discard_redo() {
+        let mut workouts = create_workouts(&["workout1"]);
+        let mut index = 0;
+        let mut settings = RotationSettings::default();
+        let mut history = UndoHistory::default();
+        let insert = |text: &str| EditCommand::Insert {
+            position: 1,
+            workout: Workout::new(text.to_owned()),
+        };
+
+        history.execute(insert("workout2"), &mut workouts, &mut index, &mut settings);
+        history.undo(&mut workouts, &mut index, &mut settings);
+        history.execute(insert("workout3"), &mut workouts, &mut index, &mut settings);
+
+        assert!(!history.can_redo());
+        assert_eq!(vec!["workout1", "workout3"], get_texts(&workouts))
+    }
+
+    #[test]
+    fn test_execute_given_full_history_should_drop_oldest() {
+        let mut workouts = vec![];
+        let mut index = 0;
+        let mut settings = RotationSettings::default();
+        let mut history = UndoHistory::default();
+
+        for i in 0..=MAX_UNDO_STEPS {
+            let command = EditCommand::Insert {
+                position: i,
+                workout: Workout::new(i.to_string()),
+            };
+            history.execute(command, &mut workouts, &mut index, &mut settings);
+        }
+        let mut undo_count = 0;
+        while history.undo(&mut workouts, &mut index, &mut settings) {
+            undo_count += 1;
+        }
+
+        assert_eq!(MAX_UNDO_STEPS, undo_count)
+    }
+
+    #[test]
+    fn test_undo_given_set_settings_should_restore_settings() {
+        let mut workouts = create_workouts(&["workout1"]);
+        let mut index = 0;
+        let mut settings = RotationSettings::default();
+        let mut history = UndoHistory::default();
+        let after = RotationSettings {
+            ordering: OrderingMode::Shuffle,
+            allow_duplicates: true,
+        };
+
+        history.execute(
+            EditCommand::SetSettings {
+                before: settings,
+                after,
+            },
+            &mut workouts,
+            &mut index,
+            &mut settings,
+        );
+        assert_eq!(after, settings);
+        history.undo(&mut workouts, &mut index, &mut settings);
+
+        assert_eq!(RotationSettings::default(), settings)
+    }
+}
@@ -0,0 +1,966 @@
+use crate::history::HistoryEntry;
+use crate::ordering::{OrderingMode, get_next_index};
+use crate::progression::{Progression, ProgressionTargets};
+use crate::schedule::ScheduleSettings;
+use chrono::NaiveDate;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workout {
+    pub id: Uuid,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exercises: Vec<Exercise>,
+    /// Relative chance of being picked by the weighted ordering.
+    #[serde(
+        alias = "weight",
+        default = "get_default_pick_weight",
+        skip_serializing_if = "is_default_pick_weight"
+    )]
+    pub pick_weight: u32,
+    /// Entries sharing a link are the same workout repeated in the rotation, so editing one
+    /// edits all of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+impl Workout {
+    pub fn new(text: String) -> Workout {
+        Workout {
+            id: Uuid::new_v4(),
+            text,
+            exercises: vec![],
+            pick_weight: DEFAULT_PICK_WEIGHT,
+            link: None,
+            notes: String::new(),
+            tags: vec![],
+            category: None,
+        }
+    }
+
+    pub fn is_linked_to(&self, other: &Workout) -> bool {
+        self.id != other.id && self.link.is_some() && self.link == other.link
+    }
+
+    /// Describes the category and tags, e.g. `Strength #upper #push`.
+    pub fn describe_labels(&self) -> Option<String> {
+        let tags = self.tags.iter().map(|t| format!("#{}", t));
+        let labels: Vec<String> = self.category.iter().cloned().chain(tags).collect();
+
+        (!labels.is_empty()).then(|| labels.join(" "))
+    }
+}
+
+/// Narrows the settings list or the rotation down to the workouts with a category or tag.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum LabelFilter {
+    Category(String),
+    Tag(String),
+}
+
+impl LabelFilter {
+    pub fn matches(&self, workout: &Workout) -> bool {
+        match self {
+            LabelFilter::Category(category) => workout.category.as_ref() == Some(category),
+            LabelFilter::Tag(tag) => workout.tags.contains(tag),
+        }
+    }
+}
+
+impl Display for LabelFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelFilter::Category(category) => write!(f, "{}", category),
+            LabelFilter::Tag(tag) => write!(f, "#{}", tag),
+        }
+    }
+}
+
+/// Returns the categories and tags in use, categories first and each sorted.
+pub fn get_label_filters(workouts: &[Workout]) -> Vec<LabelFilter> {
+    let filters: BTreeSet<LabelFilter> = workouts
+        .iter()
+        .flat_map(|w| {
+            let category = w.category.iter().cloned().map(LabelFilter::Category);
+            category.chain(w.tags.iter().cloned().map(LabelFilter::Tag))
+        })
+        .collect();
+
+    filters.into_iter().collect()
+}
+
+/// Splits comma or space separated tags, dropping a leading `#`, blanks and repeats.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+    for tag in text
+        .split([',', ' '])
+        .map(|t| t.trim().trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags
+}
+
+pub const DEFAULT_PICK_WEIGHT: u32 = 1;
+
+fn get_default_pick_weight() -> u32 {
+    DEFAULT_PICK_WEIGHT
+}
+
+fn is_default_pick_weight(pick_weight: &u32) -> bool {
+    *pick_weight == DEFAULT_PICK_WEIGHT
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Program {
+    pub id: Uuid,
+    pub name: String,
+    pub index: usize,
+    pub workouts: Vec<Workout>,
+    #[serde(default)]
+    pub ordering: OrderingMode,
+    /// Ids of the workouts done in the current shuffle cycle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycle: Vec<Uuid>,
+    #[serde(default)]
+    pub schedule: ScheduleSettings,
+    /// Lets the same workout text appear more than once, e.g. `A, B, A, C`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_duplicates: bool,
+    /// Advancing skips the workouts that do not match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<LabelFilter>,
+}
+
+impl Program {
+    pub fn new(name: String) -> Program {
+        Program {
+            id: Uuid::new_v4(),
+            name,
+            index: 0,
+            workouts: vec![],
+            ordering: OrderingMode::default(),
+            cycle: vec![],
+            schedule: ScheduleSettings::default(),
+            allow_duplicates: false,
+            filter: None,
+        }
+    }
+
+    /// Copies the program under a new name, giving it and its workouts fresh ids so that
+    /// history of the original is not attributed to the copy.
+    pub fn duplicate(&self, name: String) -> Program {
+        Program {
+            id: Uuid::new_v4(),
+            name,
+            index: self.index,
+            workouts: self
+                .workouts
+                .iter()
+                .map(|w| Workout {
+                    id: Uuid::new_v4(),
+                    ..w.clone()
+                })
+                .collect(),
+            ordering: self.ordering,
+            cycle: vec![],
+            schedule: self.schedule.clone(),
+            allow_duplicates: self.allow_duplicates,
+            filter: self.filter.clone(),
+        }
+    }
+
+    /// Moves the rotation on by the training days missed under the drop policy and records
+    /// from when the current workout is due. Returns whether the program changed.
+    pub fn apply_schedule(
+        &mut self,
+        today: NaiveDate,
+        history: &[HistoryEntry],
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        if !self.schedule.is_enabled() {
+            return false;
+        }
+
+        let missed = self.schedule.get_missed_days(today);
+        if missed == 0 && self.schedule.due_from.is_some() {
+            return false;
+        }
+
+        if !self.workouts.is_empty() {
+            for _ in 0..missed {
+                self.index = get_next_index(
+                    self.ordering,
+                    &self.workouts,
+                    self.index,
+                    self.filter.as_ref(),
+                    &mut self.cycle,
+                    history,
+                    rng,
+                );
+            }
+        }
+        self.schedule.set_due_from(today, false);
+
+        true
+    }
+}
+
+impl Default for Program {
+    fn default() -> Program {
+        Program::new("Default".to_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exercise {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sets: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reps: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<Load>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progression: Option<Progression>,
+}
+
+impl Exercise {
+    pub fn new(name: String) -> Exercise {
+        Exercise {
+            name,
+            sets: None,
+            reps: None,
+            load: None,
+            duration_secs: None,
+            progression: None,
+        }
+    }
+
+    /// Parses the editor notation `<name> [<sets>x<reps>[-<max reps>]] [@<amount><kg|lb>] [<secs>s|<mins>min|<m>:<ss>]`,
+    /// where either side of `x` may be left out.
+    /// A progression is given with `+<increment>[kg|lb]` and a rep range, or with
+    /// `<percent>%tm<amount><kg|lb>` for a percentage of a training max. The increment must be
+    /// in the unit of the load it is added to.
+    /// Targets are read from the end of the line, everything before them is the name.
+    pub fn parse(line: &str) -> Option<Exercise> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty() {
+            return None;
+        }
+
+        let (exercise, progression) = Exercise::parse_tokens(&tokens, None);
+        let (mut exercise, progression) =
+            match progression.get_conflicting_unit(exercise.load.as_ref()) {
+                None => (exercise, progression),
+                Some(unit) => Exercise::parse_tokens(&tokens, Some(unit)),
+            };
+        exercise.progression = progression.build();
+
+        Some(exercise)
+    }
+
+    /// Reads the targets from the end of the tokens, leaving increments in `rejected_unit` to
+    /// the name.
+    fn parse_tokens(
+        tokens: &[&str],
+        rejected_unit: Option<LoadUnit>,
+    ) -> (Exercise, ProgressionTargets) {
+        let mut exercise = Exercise::new(String::new());
+        let mut progression = ProgressionTargets {
+            rejected_unit,
+            ..ProgressionTargets::default()
+        };
+        let mut name_end = tokens.len();
+        while name_end > 1 {
+            let token = tokens[name_end - 1];
+            if !(token == "@" && exercise.load.is_some()
+                || exercise.apply_target(token, &mut progression))
+            {
+                break;
+            }
+
+            name_end -= 1;
+        }
+
+        exercise.name = tokens[..name_end].join(" ");
+
+        (exercise, progression)
+    }
+
+    /// Describes the targets for display, e.g. `5 × 5 · 100 kg · 1:30`.
+    pub fn describe_targets(&self) -> String {
+        let mut parts = vec![];
+        match (self.sets, self.reps) {
+            (Some(sets), Some(reps)) => parts.push(format!("{} \u{d7} {}", sets, reps)),
+            (Some(sets), None) => parts.push(format!("{} sets", sets)),
+            (None, Some(reps)) => parts.push(format!("{} reps", reps)),
+            (None, None) => (),
+        }
+        if let Some(load) = &self.load {
+            parts.push(format!("{} {}", load.amount, load.unit));
+        }
+        if let Some(secs) = self.duration_secs {
+            parts.push(format_duration(secs));
+        }
+
+        parts.join(" \u{b7} ")
+    }
+
+    fn apply_target(&mut self, token: &str, progression: &mut ProgressionTargets) -> bool {
+        let token = token.to_lowercase();
+        if progression.apply(&token) {
+            return true;
+        }
+        if token.starts_with('+') {
+            return false;
+        }
+        if self.sets.is_none()
+            && self.reps.is_none()
+            && let Some((sets, reps, max_reps)) = parse_sets_reps(&token)
+        {
+            self.sets = sets;
+            self.reps = reps;
+            progression.max_reps = max_reps;
+            return true;
+        }
+        if self.load.is_none()
+            && let Some(load) = Load::parse(&token)
+        {
+            self.load = Some(load);
+            return true;
+        }
+        if self.duration_secs.is_none()
+            && let Some(secs) = parse_duration(&token)
+        {
+            self.duration_secs = Some(secs);
+            return true;
+        }
+
+        false
+    }
+}
+
+impl Display for Exercise {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        let reps = match (self.reps, &self.progression) {
+            (Some(reps), Some(Progression::Double { max_reps, .. })) => {
+                format!("{}-{}", reps, max_reps)
+            }
+            (reps, _) => reps.map_or(String::new(), |r| r.to_string()),
+        };
+        match (self.sets, reps.is_empty()) {
+            (Some(sets), _) => write!(f, " {}x{}", sets, reps)?,
+            (None, false) => write!(f, " x{}", reps)?,
+            (None, true) => (),
+        }
+        if let Some(load) = &self.load {
+            write!(f, " @{}{}", load.amount, load.unit)?;
+        }
+        if let Some(secs) = self.duration_secs {
+            write!(f, " {}s", secs)?;
+        }
+        let load_unit = self.load.as_ref().map(|l| l.unit);
+        match &self.progression {
+            Some(Progression::Linear { increment }) => {
+                write!(f, "{}", format_increment(*increment, load_unit))?
+            }
+            Some(Progression::Double { increment, .. }) if *increment > 0.0 => {
+                write!(f, "{}", format_increment(*increment, load_unit))?
+            }
+            Some(Progression::TrainingMax {
+                percent,
+                max,
+                increment,
+            }) => {
+                write!(f, " {}%tm{}{}", percent, max.amount, max.unit)?;
+                if let Some(increment) = increment {
+                    write!(f, "{}", format_increment(*increment, Some(max.unit)))?;
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Load {
+    pub amount: f32,
+    pub unit: LoadUnit,
+}
+
+impl Load {
+    pub fn parse(token: &str) -> Option<Load> {
+        let token = token.strip_prefix('@').unwrap_or(token);
+        let (amount, unit) = if let Some(amount) = token.strip_suffix("kg") {
+            (amount, LoadUnit::Kg)
+        } else if let Some(amount) = token
+            .strip_suffix("lbs")
+            .or_else(|| token.strip_suffix("lb"))
+        {
+            (amount, LoadUnit::Lb)
+        } else {
+            return None;
+        };
+
+        amount
+            .parse::<f32>()
+            .ok()
+            .filter(|a| a.is_finite() && *a >= 0.0)
+            .map(|amount| Load { amount, unit })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadUnit {
+    Kg,
+    Lb,
+}
+
+impl Display for LoadUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadUnit::Kg => write!(f, "kg"),
+            LoadUnit::Lb => write!(f, "lb"),
+        }
+    }
+}
+
+/// Formats an increment with the unit of the load it is added to, e.g. ` +2.5kg`.
+fn format_increment(increment: f32, unit: Option<LoadUnit>) -> String {
+    format!(
+        " +{}{}",
+        increment,
+        unit.map_or(String::new(), |u| u.to_string())
+    )
+}
+
+/// A workout text is valid when it is not empty and, unless duplicates are allowed, not yet
+/// part of the rotation.
+pub fn is_valid_workout_text(text: &str, workouts: &[Workout], allow_duplicates: bool) -> bool {
+    !text.is_empty() && (allow_duplicates || workouts.iter().all(|w| w.text != text))
+}
+
+/// Numbers the entries whose text appears more than once, e.g. `A #1, B, A #2`.
+pub fn get_occurrences(workouts: &[Workout]) -> Vec<Option<usize>> {
+    workouts
+        .iter()
+        .enumerate()
+        .map(|(i, workout)| {
+            let same = |w: &&Workout| w.text == workout.text;
+            let repeated = workouts.iter().filter(same).count() > 1;
+            repeated.then(|| workouts[..=i].iter().filter(same).count())
+        })
+        .collect()
+}
+
+/// Links the workout at `position` to the first other entry with the same text and takes over
+/// its exercises. Returns `None` when there is no such entry.
+pub fn link_workout(workouts: &[Workout], position: usize) -> Option<Vec<Workout>> {
+    let workout = workouts.get(position)?;
+    let target = workouts
+        .iter()
+        .position(|w| w.id != workout.id && w.text == workout.text)?;
+    let link = workouts[target].link.unwrap_or_else(Uuid::new_v4);
+
+    let mut linked = workouts.to_vec();
+    linked[target].link = Some(link);
+    linked[position] = Workout {
+        id: workout.id,
+        ..linked[target].clone()
+    };
+    Some(linked)
+}
+
+/// Removes the workout at `position` from its link group. A partner left alone in the group
+/// is unlinked as well, so no link points at nothing.
+pub fn unlink_workout(workouts: &[Workout], position: usize) -> Vec<Workout> {
+    let mut unlinked = workouts.to_vec();
+    let link = match unlinked.get_mut(position).and_then(|w| w.link.take()) {
+        None => return unlinked,
+        Some(l) => l,
+    };
+
+    let remaining: Vec<usize> = (0..unlinked.len())
+        .filter(|p| unlinked[*p].link == Some(link))
+        .collect();
+    if let [partner] = remaining[..] {
+        unlinked[partner].link = None;
+    }
+    unlinked
+}
+
+/// Replaces the workout at `position` and copies everything but its id to the entries linked
+/// to it.
+pub fn update_linked_workouts(workouts: &mut [Workout], position: usize, after: Workout) {
+    for workout in workouts.iter_mut().filter(|w| w.is_linked_to(&after)) {
+        *workout = Workout {
+            id: workout.id,
+            ..after.clone()
+        };
+    }
+    workouts[position] = after;
+}
+
+/// Keeps the rotation index on the same workout after moving the one at `from` to `to`.
+pub fn get_index_after_move(index: usize, from: usize, to: usize) -> usize {
+    if from == index {
+        to
+    } else if from < index && to >= index {
+        index - 1
+    } else if from > index && to <= index {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Moves the workouts at `positions` as one block, in their current order, so that it starts at
+/// `to` among the remaining workouts. Returns the index of the workout that was active before.
+pub fn move_workouts(
+    workouts: &mut Vec<Workout>,
+    index: usize,
+    positions: &[usize],
+    to: usize,
+) -> usize {
+    let active = workouts.get(index).map(|w| w.id);
+    let (moved, mut remaining): (Vec<_>, Vec<_>) = workouts
+        .drain(..)
+        .enumerate()
+        .partition(|(p, _)| positions.contains(p));
+    let to = to.min(remaining.len());
+    remaining.splice(to..to, moved);
+    *workouts = remaining.into_iter().map(|(_, w)| w).collect();
+
+    workouts
+        .iter()
+        .position(|w| Some(w.id) == active)
+        .unwrap_or(0)
+}
+
+/// Keeps the rotation index pointing at a sensible workout after removing `position`.
+pub fn get_index_after_removal(index: usize, position: usize) -> usize {
+    if position <= index {
+        index.saturating_sub(1)
+    } else {
+        index
+    }
+}
+
+pub fn parse_exercises(text: &str) -> Vec<Exercise> {
+    text.lines().filter_map(Exercise::parse).collect()
+}
+
+pub fn format_exercises(exercises: &[Exercise]) -> String {
+    exercises
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn format_duration(secs: u32) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}:{:02}", s / 60, s % 60),
+        s => format!("{}:{:02}:{:02}", s / 3600, s / 60 % 60, s % 60),
+    }
+}
+
+/// Reads `<sets>x<reps>`, where the reps may be a range like `8-12`.
+fn parse_sets_reps(token: &str) -> Option<(Option<u32>, Option<u32>, Option<u32>)> {
+    let (sets, reps) = token.split_once('x')?;
+    let parse_part = |part: &str| match part {
+        "" => Some(None),
+        part => part.parse().ok().map(Some),
+    };
+    let (reps, max_reps) = match reps.split_once('-') {
+        Some((min, max)) => {
+            let min: u32 = min.parse().ok()?;
+            let max: u32 = max.parse().ok().filter(|m| *m > min)?;
+            (Some(min), Some(max))
+        }
+        None => (parse_part(reps)?, None),
+    };
+
+    match (parse_part(sets)?, reps) {
+        (None, None) => None,
+        (sets, reps) => Some((sets, reps, max_reps)),
+    }
+}
+
+fn parse_duration(token: &str) -> Option<u32> {
+    if let Some((mins, secs)) = token.split_once(':') {
+        let mins: u32 = mins.parse().ok()?;
+        let secs: u32 = secs.parse().ok().filter(|s| *s < 60)?;
+        return Some(mins * 60 + secs);
+    }
+    if let Some(mins) = token.strip_suffix("min") {
+        return mins.parse::<u32>().ok().map(|m| m * 60);
+    }
+
+    token
+        .strip_suffix("sec")
+        .or_else(|| token.strip_suffix('s'))
+        .and_then(|s| s.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schedule::{CatchUpPolicy, Schedule, ScheduleSettings};
+    use crate::workout::{
+        Exercise, LabelFilter, Load, LoadUnit, Program, Workout, format_duration, format_exercises,
+        get_index_after_move, get_index_after_removal, get_label_filters, get_occurrences,
+        is_valid_workout_text, link_workout, move_workouts, parse_exercises, parse_tags,
+        unlink_workout, update_linked_workouts,
+    };
+    use chrono::{NaiveDate, Weekday};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn create_workouts(texts: &[&str]) -> Vec<Workout> {
+        texts.iter().map(|t| Workout::new(t.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse_given_sets_reps_and_load_should_read_targets() {
+        let exercise = Exercise::parse("Back Squat 5x5 @100kg").unwrap();
+
+        assert_eq!(
+            Exercise {
+                name: String::from("Back Squat"),
+                sets: Some(5),
+                reps: Some(5),
+                load: Some(Load {
+                    amount: 100.0,
+                    unit: LoadUnit::Kg
+                }),
+                duration_secs: None,
+                progression: None,
+            },
+            exercise
+        )
+    }
+
+    #[test]
+    fn test_parse_given_detached_at_sign_should_read_load() {
+        let exercise = Exercise::parse("Bench 3x8 @ 135lbs").unwrap();
+
+        assert_eq!("Bench", exercise.name);
+        assert_eq!(
+            Some(Load {
+                amount: 135.0,
+                unit: LoadUnit::Lb
+            }),
+            exercise.load
+        )
+    }
+
+    #[test]
+    fn test_parse_given_sets_and_duration_should_read_targets() {
+        let exercise = Exercise::parse("Plank 3x 1:30").unwrap();
+
+        assert_eq!("Plank", exercise.name);
+        assert_eq!(Some(3), exercise.sets);
+        assert_eq!(None, exercise.reps);
+        assert_eq!(Some(90), exercise.duration_secs)
+    }
+
+    #[test]
+    fn test_parse_given_plain_text_should_use_text_as_name() {
+        let exercise = Exercise::parse("Easy run along the river").unwrap();
+
+        assert_eq!(
+            Exercise::new(String::from("Easy run along the river")),
+            exercise
+        )
+    }
+
+    #[test]
+    fn test_parse_given_only_target_should_use_it_as_name() {
+        let exercise = Exercise::parse("5x5").unwrap();
+
+        assert_eq!(Exercise::new(String::from("5x5")), exercise)
+    }
+
+    #[test]
+    fn test_parse_given_blank_line_should_return_none() {
+        assert_eq!(None, Exercise::parse("   "))
+    }
+
+    #[test]
+    fn test_describe_targets_should_join_targets() {
+        let exercise = Exercise::parse("Row 4x10 @22.5kg 2min").unwrap();
+
+        assert_eq!(
+            "4 \u{d7} 10 \u{b7} 22.5 kg \u{b7} 2:00",
+            exercise.describe_targets()
+        )
+    }
+
+    #[test]
+    fn test_is_valid_workout_text_given_existing_text_should_be_false() {
+        let workouts = vec![Workout::new(String::from("workout1"))];
+
+        assert!(!is_valid_workout_text("workout1", &workouts, false))
+    }
+
+    #[test]
+    fn test_is_valid_workout_text_given_existing_text_and_duplicates_allowed_should_be_true() {
+        let workouts = vec![Workout::new(String::from("workout1"))];
+
+        assert!(is_valid_workout_text("workout1", &workouts, true))
+    }
+
+    #[test]
+    fn test_get_occurrences_should_number_repeated_texts_only() {
+        let workouts = create_workouts(&["A", "B", "A", "C", "A"]);
+
+        assert_eq!(
+            vec![Some(1), None, Some(2), None, Some(3)],
+            get_occurrences(&workouts)
+        )
+    }
+
+    #[test]
+    fn test_link_workout_should_share_link_and_take_over_exercises() {
+        let mut workouts = create_workouts(&["A", "B", "A"]);
+        workouts[0].exercises = parse_exercises("Squat 5x5");
+
+        let linked = link_workout(&workouts, 2).unwrap();
+
+        assert!(linked[2].is_linked_to(&linked[0]));
+        assert_eq!(linked[0].exercises, linked[2].exercises);
+        assert_eq!(workouts[2].id, linked[2].id)
+    }
+
+    #[test]
+    fn test_link_workout_given_unique_text_should_return_none() {
+        let workouts = create_workouts(&["A", "B"]);
+
+        assert!(link_workout(&workouts, 1).is_none())
+    }
+
+    #[test]
+    fn test_unlink_workout_given_pair_should_unlink_partner() {
+        let workouts = create_workouts(&["A", "B", "A"]);
+        let workouts = link_workout(&workouts, 2).unwrap();
+
+        let unlinked = unlink_workout(&workouts, 2);
+
+        assert!(unlinked.iter().all(|w| w.link.is_none()))
+    }
+
+    #[test]
+    fn test_unlink_workout_given_larger_group_should_keep_others_linked() {
+        let workouts = create_workouts(&["A", "A", "A"]);
+        let workouts = link_workout(&workouts, 1).unwrap();
+        let workouts = link_workout(&workouts, 2).unwrap();
+
+        let unlinked = unlink_workout(&workouts, 2);
+
+        assert_eq!(None, unlinked[2].link);
+        assert!(unlinked[0].is_linked_to(&unlinked[1]))
+    }
+
+    #[test]
+    fn test_update_linked_workouts_should_propagate_to_linked_entries_only() {
+        let workouts = create_workouts(&["A", "B", "A", "A"]);
+        let mut workouts = link_workout(&workouts, 2).unwrap();
+        let after = Workout {
+            text: String::from("A2"),
+            ..workouts[0].clone()
+        };
+
+        update_linked_workouts(&mut workouts, 0, after);
+
+        assert_eq!(
+            vec!["A2", "B", "A2", "A"],
+            workouts.iter().map(|w| w.text.as_str()).collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn test_parse_tags_should_normalize_and_drop_repeats() {
+        assert_eq!(
+            vec!["upper", "push", "cardio"],
+            parse_tags("Upper, #push  cardio,upper")
+        )
+    }
+
+    #[test]
+    fn test_get_label_filters_should_list_categories_before_tags() {
+        let mut workouts = create_workouts(&["A", "B"]);
+        workouts[0].category = Some(String::from("Strength"));
+        workouts[0].tags = vec![String::from("upper")];
+        workouts[1].tags = vec![String::from("cardio"), String::from("upper")];
+
+        assert_eq!(
+            vec![
+                LabelFilter::Category(String::from("Strength")),
+                LabelFilter::Tag(String::from("cardio")),
+                LabelFilter::Tag(String::from("upper")),
+            ],
+            get_label_filters(&workouts)
+        )
+    }
+
+    #[test]
+    fn test_matches_given_tag_should_check_tags_only() {
+        let mut workout = Workout::new(String::from("A"));
+        workout.category = Some(String::from("upper"));
+
+        assert!(!LabelFilter::Tag(String::from("upper")).matches(&workout));
+        assert!(LabelFilter::Category(String::from("upper")).matches(&workout))
+    }
+
+    #[test]
+    fn test_describe_labels_should_prefix_tags() {
+        let mut workout = Workout::new(String::from("A"));
+        workout.category = Some(String::from("Strength"));
+        workout.tags = vec![String::from("upper"), String::from("push")];
+
+        assert_eq!(
+            Some(String::from("Strength #upper #push")),
+            workout.describe_labels()
+        )
+    }
+
+    #[test]
+    fn test_deserialize_given_workout_without_labels_should_default() {
+        let workout: Workout =
+            serde_json::from_str(r#"{"id":"6f1c2b8e-3f0a-4c7e-9d51-2a8b7e4c1d01","text":"Push"}"#)
+                .unwrap();
+
+        assert!(workout.notes.is_empty() && workout.tags.is_empty());
+        assert_eq!(None, workout.category)
+    }
+
+    #[test]
+    fn test_deserialize_given_legacy_weight_should_read_pick_weight() {
+        let workout: Workout = serde_json::from_str(
+            r#"{"id":"6f1c2b8e-3f0a-4c7e-9d51-2a8b7e4c1d01","text":"Push","weight":3}"#,
+        )
+        .unwrap();
+
+        assert_eq!(3, workout.pick_weight)
+    }
+
+    #[test]
+    fn test_apply_schedule_given_drop_policy_should_advance_and_mark_due_today() {
+        let mut program = Program {
+            workouts: create_workouts(&["A", "B", "C"]),
+            schedule: ScheduleSettings {
+                schedule: Schedule::Weekdays {
+                    days: vec![Weekday::Mon, Weekday::Wed],
+                },
+                policy: CatchUpPolicy::Drop,
+                due_from: NaiveDate::from_ymd_opt(2025, 9, 1),
+            },
+            ..Program::default()
+        };
+        let friday = NaiveDate::from_ymd_opt(2025, 9, 5).unwrap();
+
+        assert!(program.apply_schedule(friday, &[], &mut StdRng::seed_from_u64(1)));
+        assert!(!program.apply_schedule(friday, &[], &mut StdRng::seed_from_u64(1)));
+
+        assert_eq!(2, program.index);
+        assert_eq!(Some(friday), program.schedule.due_from)
+    }
+
+    #[test]
+    fn test_get_index_after_move_should_follow_active_workout() {
+        for (from, to) in [(0, 3), (3, 0), (2, 4), (4, 1), (0, 1), (3, 4)] {
+            let mut workouts = create_workouts(&["0", "1", "2", "3", "4"]);
+            let index = get_index_after_move(2, from, to);
+            let workout = workouts.remove(from);
+            workouts.insert(to, workout);
+
+            assert_eq!("2", workouts[index].text);
+        }
+    }
+
+    #[test]
+    fn test_move_workouts_given_block_to_top_should_keep_order_and_active_workout() {
+        let mut workouts = create_workouts(&["A", "B", "C", "D", "E"]);
+
+        let index = move_workouts(&mut workouts, 2, &[1, 3], 0);
+
+        assert_eq!(
+            vec!["B", "D", "A", "C", "E"],
+            workouts.iter().map(|w| w.text.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(3, index)
+    }
+
+    #[test]
+    fn test_move_workouts_given_target_beyond_end_should_append() {
+        let mut workouts = create_workouts(&["A", "B", "C"]);
+
+        let index = move_workouts(&mut workouts, 0, &[0], 10);
+
+        assert_eq!(
+            vec!["B", "C", "A"],
+            workouts.iter().map(|w| w.text.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(2, index)
+    }
+
+    #[test]
+    fn test_get_index_after_removal_given_removal_before_index_should_decrement() {
+        assert_eq!(1, get_index_after_removal(2, 0))
+    }
+
+    #[test]
+    fn test_get_index_after_removal_given_removal_after_index_should_keep_index() {
+        assert_eq!(2, get_index_after_removal(2, 3))
+    }
+
+    #[test]
+    fn test_format_duration_given_hours_should_include_hours() {
+        assert_eq!("1:05:09", format_duration(3909))
+    }
+
+    #[test]
+    fn test_format_exercises_should_keep_increment_unit() {
+        let exercises = parse_exercises("Squat 5x5 @225lb +5\nBench 5x5 80%tm100kg +2.5");
+
+        assert_eq!(
+            "Squat 5x5 @225lb +5lb\nBench 5x5 80%tm100kg +2.5kg",
+            format_exercises(&exercises)
+        )
+    }
+
+    #[test]
+    fn test_format_exercises_should_round_trip() {
+        let text = "Squat 5x5 @102.5kg\nPlank 3x 45s\nPush-ups x20\n\nStretching\n\
+            Curl 3x8-12 @10kg +1\nBench 5x5 80%tm100kg +2.5\nPress 5x5 @40kg +2.5";
+
+        let exercises = parse_exercises(text);
+
+        assert_eq!(7, exercises.len());
+        assert_eq!(exercises, parse_exercises(&format_exercises(&exercises)))
+    }
+}